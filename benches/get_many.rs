@@ -0,0 +1,36 @@
+//! Compares [SparseVec::get_many] with repeated calls to [SparseVec::get].
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+#![feature(allocator_api,test)]
+
+extern crate test;
+
+use sparse_vec::SparseVec;
+use std::alloc::Global;
+use test::{Bencher,black_box};
+
+/// Count of values stored in the SparseVec.
+const COUNT: usize = 100_000;
+
+/// Constructs a SparseVec holding every third index and a sorted probe of every index.
+fn setup() -> (SparseVec<u64, Global>, Vec<usize>) {
+  let sparse_vec = (0..COUNT).map(|index| (index * 3,index as u64)).collect();
+  let probes = (0..COUNT * 3).collect();
+
+  (sparse_vec,probes)
+}
+
+#[bench]
+fn get_repeated(bencher: &mut Bencher) {
+  let (sparse_vec,probes) = setup();
+
+  bencher.iter(|| probes.iter().filter_map(|&index| sparse_vec.get(black_box(index))).sum::<u64>())
+}
+
+#[bench]
+fn get_many(bencher: &mut Bencher) {
+  let (sparse_vec,probes) = setup();
+
+  bencher.iter(|| sparse_vec.get_many(black_box(&probes)).filter_map(|(_,value)| value).sum::<u64>())
+}
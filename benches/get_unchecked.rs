@@ -0,0 +1,45 @@
+//! Compares [SparseVec::get_unchecked] with [SparseVec::get] on indices known to be set.
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+#![feature(allocator_api,test)]
+
+extern crate test;
+
+use sparse_vec::SparseVec;
+use std::alloc::Global;
+use test::{Bencher,black_box};
+
+/// Count of values stored in the SparseVec.
+const COUNT: usize = 100_000;
+
+/// Constructs a SparseVec holding every third index and a probe of every set index.
+fn setup() -> (SparseVec<u64, Global>, Vec<usize>) {
+  let sparse_vec = (0..COUNT).map(|index| (index * 3,index as u64)).collect();
+  let probes = (0..COUNT).map(|index| index * 3).collect();
+
+  (sparse_vec,probes)
+}
+
+#[bench]
+fn get(bencher: &mut Bencher) {
+  let (sparse_vec,probes) = setup();
+
+  bencher.iter(|| probes.iter().map(|&index| sparse_vec.get(black_box(index)).unwrap()).sum::<u64>())
+}
+
+#[bench]
+fn get_unchecked(bencher: &mut Bencher) {
+  let (sparse_vec,probes) = setup();
+
+  // Every probe is set.
+  bencher.iter(|| probes.iter().map(|&index| unsafe { sparse_vec.get_unchecked(black_box(index)) }).sum::<u64>())
+}
+
+#[bench]
+fn value_at_slot_unchecked(bencher: &mut Bencher) {
+  let (sparse_vec,_) = setup();
+
+  // Every slot is less than the count.
+  bencher.iter(|| (0..COUNT).map(|slot| unsafe { sparse_vec.value_at_slot_unchecked(black_box(slot)) }).sum::<u64>())
+}
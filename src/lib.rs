@@ -1,12 +1,12 @@
 //! Defines a sparse vector container.
 //!
 //! Author --- DMorgan  
-//! Last Modified --- 2025-02-03
+//! Last Modified --- 2026-10-14
 #![no_std]
 #![deny(missing_docs)]
-#![feature(allocator_api,box_vec_non_null,const_vec_string_slice)]
+#![feature(allocator_api)]
 
-pub use sparse_vecs::SparseVec;
+pub use sparse_vecs::{SparseVec,Entry,OccupiedEntry,VacantEntry,IntoIter,Drain,ExtractIf,MoveError,OffsetError,DuplicateIndexError,FromPartsError,FromPartsErrorKind,SortedInputError};
 
 extern crate alloc;
 
@@ -1,18 +1,37 @@
 //! Defines the [SparseVec] type.
 //!
 //! Author --- DMorgan  
-//! Last Modified --- 2025-02-03
+//! Last Modified --- 2026-10-14
 
 use alloc::alloc::Allocator;
+use alloc::collections::TryReserveError;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self,Debug,Display};
+use core::iter;
+use core::hash::{Hash,Hasher};
 use core::mem;
-use core::ops::{Index,IndexMut};
+use core::ptr;
+use core::ops::{Bound,Index,IndexMut,Range,RangeBounds,RangeInclusive};
+
+pub use entries::{Entry,OccupiedEntry,VacantEntry};
+pub use errors::{MoveError,OffsetError,DuplicateIndexError,FromPartsError,FromPartsErrorKind,SortedInputError};
+pub use iters::{IntoIter,Drain,ExtractIf};
+
+mod entries;
+mod errors;
+mod iters;
+mod ops;
+#[cfg(feature = "serde")]
+mod serdes;
 
 /// Sparse list of values.
 ///
 /// Maintains separate lists of indices and values.
-#[derive(Clone)]
-pub struct SparseVec<T, Alloc>
+///
+/// `Idx` is the type of the external indices; a smaller type such as `u32` shrinks the indices
+/// list. Methods which do arithmetic on indices are only provided for `usize`.
+pub struct SparseVec<T, Alloc, Idx = usize>
   where Alloc: Allocator {
   /// External indices of each position in `values`.
   ///
@@ -21,7 +40,7 @@ pub struct SparseVec<T, Alloc>
   /// * Parallel array with `values`.
   /// * Indices are unique.
   /// * Indices are sorted.
-  indices: Vec<usize,Alloc>,
+  indices: Vec<Idx,Alloc>,
   /// Stored values.
   ///
   /// # Invariants
@@ -30,20 +49,37 @@ pub struct SparseVec<T, Alloc>
   values: Vec<T,Alloc>,
 }
 
-impl<T, Alloc> SparseVec<T, Alloc>
-  where Alloc: Allocator {
+impl<T, Alloc, Idx> SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
   /// Deconstructs a SparseVec into parts.
   ///
   /// Returns `(Indices, Values)`.
-  pub const fn into_parts(self) -> (Vec<usize, Alloc>, Vec<T, Alloc>) {
-    use core::ptr;
-
+  pub const fn into_parts(self) -> (Vec<Idx, Alloc>, Vec<T, Alloc>) {
     let indices = unsafe { ptr::read(&self.indices) };
     let values = unsafe { ptr::read(&self.values) };
 
     mem::forget(self);
     (indices,values)
   }
+  /// Deconstructs a SparseVec into its indices.
+  ///
+  /// The indices keep their original allocation.
+  pub fn into_indices(self) -> Vec<Idx, Alloc> { self.into_parts().0 }
+  /// Deconstructs a SparseVec into its values in index order.
+  ///
+  /// The values keep their original allocation.
+  pub fn into_values(self) -> Vec<T, Alloc> { self.into_parts().1 }
+  /// Takes the values in index order, leaving `self` empty.
+  ///
+  /// The values keep their original allocation. `self` keeps the allocation of its indices and
+  /// gets a new, empty allocation for its values.
+  pub fn take_values(&mut self) -> Vec<T, Alloc>
+    where Alloc: Clone {
+    let values = Vec::new_in(self.values.allocator().clone());
+
+    self.indices.clear();
+    mem::replace(&mut self.values,values)
+  }
   /// Constructs a SparseVec from parts.
   ///
   /// # Params
@@ -54,9 +90,88 @@ impl<T, Alloc> SparseVec<T, Alloc>
   /// # Safety
   ///
   /// * `indices` must be unique and sorted.  
-  pub const unsafe fn from_parts(indices: Vec<usize,Alloc>, values: Vec<T,Alloc>) -> Self {
+  /// * `indices` and `values` must be the same length.  
+  pub const unsafe fn from_parts(indices: Vec<Idx,Alloc>, values: Vec<T,Alloc>) -> Self {
     Self{indices,values}
   }
+  /// Constructs a SparseVec from parts, checking the invariants.
+  ///
+  /// # Params
+  ///
+  /// indices --- External indices of each position in `values`.  
+  /// values --- Stored values.  
+  ///
+  /// # Errors
+  ///
+  /// * If `indices` and `values` are different lengths.
+  /// * If `indices` is not unique and sorted.
+  ///
+  /// The error holds `indices` and `values` so that they can be recovered.
+  pub fn try_from_parts(indices: Vec<Idx,Alloc>, values: Vec<T,Alloc>) -> Result<Self, FromPartsError<T, Alloc, Idx>> {
+    let kind = if indices.len() != values.len() {
+      Some(FromPartsErrorKind::LengthMismatch{indices: indices.len(),values: values.len()})
+    } else {
+      indices.windows(2).enumerate().find_map(|(position,pair)| match pair[0].cmp(&pair[1]) {
+        Ordering::Less => None,
+        Ordering::Equal => Some(FromPartsErrorKind::Duplicate{at: position + 1}),
+        Ordering::Greater => Some(FromPartsErrorKind::NotSorted{at: position + 1}),
+      })
+    };
+
+    if let Some(kind) = kind { return Err(FromPartsError{kind,indices,values}) }
+
+    Ok(unsafe { Self::from_parts(indices,values) })
+  }
+  /// Constructs a SparseVec from `(index, value)` pairs in strictly ascending index order.
+  ///
+  /// # Params
+  ///
+  /// iter --- `(index, value)` pairs to collect.  
+  /// allocator --- Allocator of the SparseVec.  
+  ///
+  /// # Errors
+  ///
+  /// * If an index is not greater than the index before it.
+  pub fn from_sorted_iter<I>(iter: I, allocator: Alloc) -> Result<Self, SortedInputError<Idx>>
+    where Alloc: Clone, I: IntoIterator<Item = (Idx, T)> {
+    let iter = iter.into_iter();
+    let mut sparse_vec = Self::with_capacity_in(iter.size_hint().0,allocator);
+
+    for (position,(index,value)) in iter.enumerate() {
+      if sparse_vec.indices.last().is_some_and(|&last| last >= index) {
+        return Err(SortedInputError{position,index})
+      }
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value);
+    }
+
+    Ok(sparse_vec)
+  }
+  /// Constructs a SparseVec from `(index, value)` pairs without checking their order.
+  ///
+  /// # Params
+  ///
+  /// iter --- `(index, value)` pairs to collect.  
+  /// allocator --- Allocator of the SparseVec.  
+  ///
+  /// # Safety
+  ///
+  /// * The indices of `iter` must be strictly ascending.  
+  pub unsafe fn from_sorted_iter_unchecked<I>(iter: I, allocator: Alloc) -> Self
+    where Alloc: Clone, I: IntoIterator<Item = (Idx, T)> {
+    let iter = iter.into_iter();
+    let mut sparse_vec = Self::with_capacity_in(iter.size_hint().0,allocator);
+
+    for (index,value) in iter {
+      debug_assert!(sparse_vec.indices.last().is_none_or(|&last| last < index),"got unsorted indices");
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value);
+    }
+
+    sparse_vec
+  }
   /// Constructs an empty SparseVec.
   ///
   /// # Params
@@ -102,30 +217,412 @@ impl<T, Alloc> SparseVec<T, Alloc>
 
     unsafe { Self::from_parts(indices,values) }
   }
+  /// Collects `(index, value)` pairs in any order, combining the values of repeated indices.
+  ///
+  /// # Params
+  ///
+  /// iter --- `(index, value)` pairs to collect.  
+  /// merge --- Combines the earlier and later values of a repeated index.  
+  pub fn from_iter_with<I, F>(iter: I, merge: F) -> Self
+    where Alloc: Default, I: IntoIterator<Item = (Idx, T)>, F: FnMut(Idx, T, T) -> T {
+    Self::collect_unsorted(iter,Alloc::default,merge)
+  }
   /// Returns the number of stored values.
   pub const fn count(&self) -> usize { self.indices.len() }
+  /// Returns the number of values which can be stored without reallocating.
+  ///
+  /// `indices` and `values` grow together so only the smaller capacity is usable.
+  pub const fn capacity(&self) -> usize {
+    let indices = self.indices.capacity();
+    let values = self.values.capacity();
+
+    if indices < values { indices } else { values }
+  }
+  /// Estimates the heap memory used in bytes.
+  ///
+  /// Ignores any heap memory owned by the values themselves.
+  pub const fn memory_usage(&self) -> usize {
+    self.indices.capacity() * mem::size_of::<Idx>() + self.values.capacity() * mem::size_of::<T>()
+  }
   /// Tests is `self` is empty.
   pub const fn is_empty(&self) -> bool { self.indices.is_empty() }
+  /// Returns the set indices.
+  ///
+  /// The indices are unique, sorted and parallel with [values_slice](Self::values_slice).
+  pub const fn indices_slice(&self) -> &[Idx] { self.indices.as_slice() }
+  /// Returns the stored values.
+  ///
+  /// The values are in index order and parallel with [indices_slice](Self::indices_slice).
+  pub const fn values_slice(&self) -> &[T] { self.values.as_slice() }
+  /// Returns the count of set indices in `range`.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to count.  
+  pub fn count_in_range<R>(&self, range: R) -> usize
+    where R: RangeBounds<Idx> {
+    self.position_range(range).len()
+  }
+  /// Returns the stored values.
+  ///
+  /// The values are in index order and parallel with [indices_slice](Self::indices_slice).
+  pub const fn values_slice_mut(&mut self) -> &mut [T] { self.values.as_mut_slice() }
+  /// Returns the set indices and the stored values.
+  ///
+  /// Returns `(Indices, Values)` as from [indices_slice](Self::indices_slice) and
+  /// [values_slice](Self::values_slice).
+  pub const fn as_parts(&self) -> (&[Idx], &[T]) { (self.indices_slice(),self.values_slice()) }
   /// Tests if `index` holds a value.
-  pub fn is_set(&self, index: usize) -> bool {
+  pub fn is_set(&self, index: Idx) -> bool {
     self.indices.as_slice().binary_search(&index).is_ok()
   }
+  /// Gets the value at `index` without checking that it is set.
+  ///
+  /// # Safety
+  ///
+  /// * `index` must be set.  
+  pub unsafe fn get_unchecked(&self, index: Idx) -> &T {
+    debug_assert!(self.is_set(index),"accessed an empty index");
+
+    let slot = unsafe { self.indices.binary_search(&index).unwrap_unchecked() };
+
+    unsafe { self.values.get_unchecked(slot) }
+  }
+  /// Gets the value at `index` without checking that it is set.
+  ///
+  /// # Safety
+  ///
+  /// * `index` must be set.  
+  pub unsafe fn get_unchecked_mut(&mut self, index: Idx) -> &mut T {
+    debug_assert!(self.is_set(index),"accessed an empty index");
+
+    let slot = unsafe { self.indices.binary_search(&index).unwrap_unchecked() };
+
+    unsafe { self.values.get_unchecked_mut(slot) }
+  }
+  /// Gets the value stored at `slot` without bounds checking.
+  ///
+  /// # Safety
+  ///
+  /// * `slot` must be less than [count](Self::count).  
+  pub unsafe fn value_at_slot_unchecked(&self, slot: usize) -> &T {
+    debug_assert!(slot < self.count(),"accessed an out of bounds slot");
+
+    unsafe { self.values.get_unchecked(slot) }
+  }
+  /// Gets the value stored at `slot` without bounds checking.
+  ///
+  /// # Safety
+  ///
+  /// * `slot` must be less than [count](Self::count).  
+  pub unsafe fn value_at_slot_unchecked_mut(&mut self, slot: usize) -> &mut T {
+    debug_assert!(slot < self.count(),"accessed an out of bounds slot");
+
+    unsafe { self.values.get_unchecked_mut(slot) }
+  }
+  /// Tests if any stored value equals `value`.
+  ///
+  /// Unlike [is_set](Self::is_set) this is a linear scan as the values are not sorted.
+  pub fn contains_value(&self, value: &T) -> bool
+    where T: PartialEq {
+    self.values.contains(value)
+  }
+  /// Tests if every index in `sorted` holds a value.
+  ///
+  /// # Params
+  ///
+  /// sorted --- Sorted external indices to test.  
+  pub fn contains_all(&self, sorted: &[Idx]) -> bool {
+    debug_assert!(sorted.is_sorted(),"tested unsorted indices");
+
+    let mut indices = self.indices.iter().peekable();
+
+    sorted.iter().all(|&index| {
+      while indices.next_if(|&&other| other < index).is_some() {}
+
+      indices.peek() == Some(&&index)
+    })
+  }
+  /// Tests if any index in `sorted` holds a value.
+  ///
+  /// # Params
+  ///
+  /// sorted --- Sorted external indices to test.  
+  pub fn contains_any(&self, sorted: &[Idx]) -> bool {
+    debug_assert!(sorted.is_sorted(),"tested unsorted indices");
+
+    let mut indices = self.indices.iter().peekable();
+
+    sorted.iter().any(|&index| {
+      while indices.next_if(|&&other| other < index).is_some() {}
+
+      indices.peek() == Some(&&index)
+    })
+  }
   /// Gets the value at `index`.
   ///
   /// Returns `None` if `index` is unset.
-  pub fn get(&self, index: usize) -> Option<&T> {
+  pub fn get(&self, index: Idx) -> Option<&T> {
     let value_index = self.indices.binary_search(&index).ok()?;
 
     Some(unsafe { self.values.get_unchecked(value_index) })
   }
+  /// Gets the value at each index in `sorted` in a single walk.
+  ///
+  /// Yields `(index, value)` for each index in `sorted`, repeated indices included.
+  ///
+  /// # Params
+  ///
+  /// sorted --- Sorted external indices to get.  
+  pub fn get_many<'a>(&'a self, sorted: &'a [Idx]) -> impl ExactSizeIterator<Item = (Idx, Option<&'a T>)> + Clone {
+    debug_assert!(sorted.is_sorted(),"got unsorted indices");
+
+    let mut slot = 0;
+
+    sorted.iter().map(move |&index| {
+      while self.indices.get(slot).is_some_and(|&other| other < index) { slot += 1 }
+
+      (index,self.indices.get(slot).filter(|&&other| other == index).map(|_| &self.values[slot]))
+    })
+  }
+  /// Gets the value at `index`, searching outwards from `hint`.
+  ///
+  /// Returns the value, if any, and the slot of `index` to use as the next hint. Any `hint` is
+  /// valid but lookups are fastest when `index` is in a nearby slot.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to get.  
+  /// hint --- Slot to start searching from.  
+  pub fn get_with_hint(&self, index: Idx, hint: usize) -> (Option<&T>, usize) {
+    match self.search_with_hint(index,hint) {
+      Ok(slot) => (Some(unsafe { self.values.get_unchecked(slot) }),slot),
+      Err(slot) => (None,slot),
+    }
+  }
   /// Gets the value at `index`.
   ///
   /// Returns `None` if `index` is unset.
-  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+  pub fn get_mut(&mut self, index: Idx) -> Option<&mut T> {
     let value_index = self.indices.binary_search(&index).ok()?;
 
     Some(unsafe { self.values.get_unchecked_mut(value_index) })
   }
+  /// Gets the value at `index`, storing `f()` first if `index` is unset.
+  ///
+  /// `f` is only called if `index` is unset.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to get.  
+  /// f --- Constructs the value to store if `index` is unset.  
+  pub fn get_or_insert_with<F>(&mut self, index: Idx, f: F) -> &mut T
+    where F: FnOnce() -> T {
+    self.entry(index).or_insert_with(f)
+  }
+  /// Gets the value at `index`, storing the default value first if `index` is unset.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to get.  
+  pub fn get_or_insert_default(&mut self, index: Idx) -> &mut T
+    where T: Default {
+    self.entry(index).or_default()
+  }
+  /// Locates the slot of `index`.
+  ///
+  /// Slots are positions among the stored values. They are invalidated by any operation which sets
+  /// or unsets an index.
+  ///
+  /// Returns `Ok(slot)` if `index` is set or `Err(slot)` with the slot `index` would be stored at.
+  pub fn slot_of(&self, index: Idx) -> Result<usize, usize> { self.indices.binary_search(&index) }
+  /// Returns the first slot whose index is greater than or equal to `index`.
+  ///
+  /// Returns [count](Self::count) if there is no such slot.
+  pub fn lower_bound(&self, index: Idx) -> usize { self.rank(index) }
+  /// Returns the first slot whose index is greater than `index`.
+  ///
+  /// Returns [count](Self::count) if there is no such slot.
+  pub fn upper_bound(&self, index: Idx) -> usize {
+    self.indices.partition_point(|&other| other <= index)
+  }
+  /// Returns the index stored at `slot`.
+  ///
+  /// Returns `None` if `slot` is out of bounds.
+  pub fn index_at_slot(&self, slot: usize) -> Option<Idx> { self.indices.get(slot).copied() }
+  /// Gets the value stored at `slot`.
+  ///
+  /// Returns `None` if `slot` is out of bounds.
+  pub fn get_at_slot(&self, slot: usize) -> Option<&T> { self.values.get(slot) }
+  /// Gets the value stored at `slot`.
+  ///
+  /// Returns `None` if `slot` is out of bounds.
+  pub fn get_at_slot_mut(&mut self, slot: usize) -> Option<&mut T> { self.values.get_mut(slot) }
+  /// Unsets the index stored at `slot` and returns it with its value.
+  ///
+  /// Returns `None` if `slot` is out of bounds.
+  pub fn remove_at_slot(&mut self, slot: usize) -> Option<(Idx, T)> {
+    if slot >= self.count() { return None }
+
+    Some((self.indices.remove(slot),self.values.remove(slot)))
+  }
+  /// Gets the `n`th set index and its value, counting from the lowest set index.
+  ///
+  /// Unlike [get](Self::get) `n` is a position among the stored values rather than an external index.
+  ///
+  /// Returns `None` if fewer than `n + 1` values are stored.
+  pub fn nth(&self, n: usize) -> Option<(Idx, &T)> {
+    Some((*self.indices.get(n)?,self.values.get(n)?))
+  }
+  /// Returns the count of set indices less than `index`.
+  pub fn rank(&self, index: Idx) -> usize {
+    self.indices.partition_point(|&other| other < index)
+  }
+  /// Gets the `n`th set index and its value, counting from the lowest set index.
+  ///
+  /// If `index` is set `select(rank(index))` gets `index`.
+  ///
+  /// Returns `None` if fewer than `n + 1` values are stored.
+  pub fn select(&self, n: usize) -> Option<(Idx, &T)> { self.nth(n) }
+  /// Gets the `n`th set index and its value, counting from the lowest set index.
+  ///
+  /// Returns `None` if fewer than `n + 1` values are stored.
+  pub fn select_mut(&mut self, n: usize) -> Option<(Idx, &mut T)> {
+    Some((*self.indices.get(n)?,self.values.get_mut(n)?))
+  }
+  /// Returns the lowest set index.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn first_index(&self) -> Option<Idx> {
+    match self.indices.as_slice().first() {
+      Some(&index) => Some(index),
+      None => None,
+    }
+  }
+  /// Returns the highest set index.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn last_index(&self) -> Option<Idx> {
+    match self.indices.as_slice().last() {
+      Some(&index) => Some(index),
+      None => None,
+    }
+  }
+  /// Gets the lowest set index and its value.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn first(&self) -> Option<(Idx, &T)> {
+    match (self.indices.as_slice().first(),self.values.as_slice().first()) {
+      (Some(&index),Some(value)) => Some((index,value)),
+      _ => None,
+    }
+  }
+  /// Gets the lowest set index and its value.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn first_mut(&mut self) -> Option<(Idx, &mut T)> {
+    match (self.indices.as_slice().first(),self.values.as_mut_slice().first_mut()) {
+      (Some(&index),Some(value)) => Some((index,value)),
+      _ => None,
+    }
+  }
+  /// Gets the highest set index and its value.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn last(&self) -> Option<(Idx, &T)> {
+    match (self.indices.as_slice().last(),self.values.as_slice().last()) {
+      (Some(&index),Some(value)) => Some((index,value)),
+      _ => None,
+    }
+  }
+  /// Gets the highest set index and its value.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub const fn last_mut(&mut self) -> Option<(Idx, &mut T)> {
+    match (self.indices.as_slice().last(),self.values.as_mut_slice().last_mut()) {
+      (Some(&index),Some(value)) => Some((index,value)),
+      _ => None,
+    }
+  }
+  /// Returns the lowest set index greater than or equal to `from`.
+  ///
+  /// Returns `None` if no such index is set.
+  pub fn next_set_index(&self, from: Idx) -> Option<Idx> {
+    self.next_set_entry(from).map(|(index,_)| index)
+  }
+  /// Gets the lowest set index greater than or equal to `from` and its value.
+  ///
+  /// Returns `None` if no such index is set.
+  pub fn next_set_entry(&self, from: Idx) -> Option<(Idx, &T)> {
+    self.nth(self.indices.partition_point(|&index| index < from))
+  }
+  /// Returns the highest set index less than or equal to `from`.
+  ///
+  /// Returns `None` if no such index is set.
+  pub fn prev_set_index(&self, from: Idx) -> Option<Idx> {
+    self.prev_set_entry(from).map(|(index,_)| index)
+  }
+  /// Gets the highest set index less than or equal to `from` and its value.
+  ///
+  /// Returns `None` if no such index is set.
+  pub fn prev_set_entry(&self, from: Idx) -> Option<(Idx, &T)> {
+    self.nth(self.indices.partition_point(|&index| index <= from).checked_sub(1)?)
+  }
+  /// Gets the values at each of `indices`.
+  ///
+  /// Returns `None` if any index is unset or repeated.
+  ///
+  /// # Params
+  ///
+  /// indices --- External indices to get.  
+  pub fn get_many_mut<const N: usize>(&mut self, indices: [Idx; N]) -> Option<[&mut T; N]> {
+    let mut positions = [0; N];
+
+    for (position,index) in positions.iter_mut().zip(indices) {
+      *position = self.indices.binary_search(&index).ok()?;
+    }
+
+    self.values.get_disjoint_mut(positions).ok()
+  }
+  /// Gets the values at each of `indices`.
+  ///
+  /// Unset indices get `None`.
+  ///
+  /// # Params
+  ///
+  /// indices --- External indices to get.  
+  ///
+  /// # Panics
+  ///
+  /// * If any index is repeated.
+  #[track_caller]
+  pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [Idx; N]) -> [Option<&mut T>; N] {
+    for (position,index) in indices.iter().enumerate() {
+      if indices[..position].contains(index) { panic!("got a repeated index") }
+    }
+
+    let values = self.values.as_mut_ptr();
+
+    indices.map(|index| {
+      let position = self.indices.binary_search(&index).ok()?;
+
+      // The indices are unique so each position is unique and no two references alias.
+      Some(unsafe { &mut *values.add(position) })
+    })
+  }
+  /// Gets the values at `a` and `b`.
+  ///
+  /// Unset indices get `None`.
+  ///
+  /// # Panics
+  ///
+  /// * If `a` equals `b`.
+  #[track_caller]
+  pub fn get2_mut(&mut self, a: Idx, b: Idx) -> (Option<&mut T>, Option<&mut T>) {
+    let [a,b] = self.get_disjoint_mut([a,b]);
+
+    (a,b)
+  }
   /// Reserves `space` more positions.
   ///
   /// See [Vec::reserve].
@@ -133,8 +630,42 @@ impl<T, Alloc> SparseVec<T, Alloc>
     self.indices.reserve(space);
     self.values.reserve(space);
   }
+  /// Reserves exactly `space` more positions.
+  ///
+  /// See [Vec::reserve_exact].
+  pub fn reserve_exact(&mut self, space: usize) {
+    self.indices.reserve_exact(space);
+    self.values.reserve_exact(space);
+  }
+  /// Tries to reserve `space` more positions.
+  ///
+  /// `values` is only reserved if reserving `indices` succeeds.
+  ///
+  /// See [Vec::try_reserve].
+  pub fn try_reserve(&mut self, space: usize) -> Result<(), TryReserveError> {
+    self.indices.try_reserve(space)?;
+    self.values.try_reserve(space)
+  }
+  /// Shrinks the capacity as much as possible.
+  ///
+  /// See [Vec::shrink_to_fit].
+  pub fn shrink_to_fit(&mut self) {
+    self.indices.shrink_to_fit();
+    self.values.shrink_to_fit();
+  }
+  /// Shrinks the capacity to at least `min_capacity`.
+  ///
+  /// See [Vec::shrink_to].
+  ///
+  /// # Params
+  ///
+  /// min_capacity --- Count of values to keep space for.  
+  pub fn shrink_to(&mut self, min_capacity: usize) {
+    self.indices.shrink_to(min_capacity);
+    self.values.shrink_to(min_capacity);
+  }
   /// Stores `value` at `index` and returns any previously stored value.
-  pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+  pub fn set(&mut self, index: Idx, value: T) -> Option<T> {
     match self.indices.binary_search(&index) {
       Ok(value_index) => Some(mem::replace(&mut self.values[value_index],value)),
       Err(value_index) => {
@@ -145,45 +676,2684 @@ impl<T, Alloc> SparseVec<T, Alloc>
       },
     }
   }
-  /// Iterates over all set indices.
-  pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + Clone {
-    self.indices.iter().copied().zip(self.values.iter())
-  }
-  /// Iterates over all set indices.
-  pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
-    self.indices.iter().copied().zip(self.values.iter_mut())
-  }
-}
-
-impl<T,Alloc> Default for SparseVec<T,Alloc>
-  where Alloc: Allocator + Default {
-  fn default() -> Self { Self::new() }
-}
-
-impl<T, Alloc> Index<usize> for SparseVec<T, Alloc>
-  where Alloc: Allocator {
-  type Output = T;
+  /// Stores `value` at `index` and returns any previously stored value, reporting allocation
+  /// failure instead of aborting.
+  ///
+  /// Space is reserved in both lists before either is modified.
+  ///
+  /// # Errors
+  ///
+  /// * If the space for `index` could not be allocated. `self` is left unmodified and `value` is
+  ///   dropped.
+  pub fn try_set(&mut self, index: Idx, value: T) -> Result<Option<T>, TryReserveError> {
+    match self.indices.binary_search(&index) {
+      Ok(value_index) => Ok(Some(mem::replace(&mut self.values[value_index],value))),
+      Err(value_index) => {
+        self.try_reserve(1)?;
+        self.indices.insert(value_index,index);
+        self.values.insert(value_index,value);
 
+        Ok(None)
+      },
+    }
+  }
+  /// Stores each `(index, value)` pair of `pairs` in a single merge.
+  ///
+  /// Replaces any values already stored at the indices.
+  ///
+  /// # Params
+  ///
+  /// pairs --- Pairs to store, sorted by unique index.  
+  ///
+  /// # Panics
+  ///
+  /// * If `pairs` is not sorted by unique index. `self` is left unmodified.
   #[track_caller]
-  fn index(&self, index: usize) -> &Self::Output {
-    self.get(index).expect("accessed and empty index")
+  pub fn set_many<I>(&mut self, pairs: I)
+    where Alloc: Clone, I: IntoIterator<Item = (Idx, T)> {
+    let mut buffer = Vec::new_in(self.indices.allocator().clone());
+
+    buffer.extend(pairs);
+    // Checked before merging as an unsorted merge would break the invariants.
+    assert!(buffer.is_sorted_by(|(lhs,_),(rhs,_)| lhs < rhs),"stored unsorted indices");
+    self.merge_sorted(buffer,|_,_,value| value)
   }
-}
+  /// Stores `value` at `index`, searching outwards from `hint`.
+  ///
+  /// Returns any previously stored value and the slot of `index` to use as the next hint. Any
+  /// `hint` is valid but stores are fastest when `index` is in a nearby slot.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to store at.  
+  /// value --- Value to store.  
+  /// hint --- Slot to start searching from.  
+  pub fn set_with_hint(&mut self, index: Idx, value: T, hint: usize) -> (Option<T>, usize) {
+    match self.search_with_hint(index,hint) {
+      Ok(slot) => (Some(mem::replace(&mut self.values[slot],value)),slot),
+      Err(slot) => {
+        self.indices.insert(slot,index);
+        self.values.insert(slot,value);
 
-impl<T, Alloc> IndexMut<usize> for SparseVec<T, Alloc>
-  where Alloc: Allocator {
-  #[track_caller]
-  fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-    self.get_mut(index).expect("accessed and empty index")
+        (None,slot)
+      },
+    }
   }
-}
+  /// Unsets `index` and returns any previously stored value.
+  pub fn remove(&mut self, index: Idx) -> Option<T> {
+    let value_index = self.indices.binary_search(&index).ok()?;
 
-impl<T,Alloc> Eq for SparseVec<T,Alloc>
-  where T: Eq, Alloc: Allocator {}
+    self.indices.remove(value_index);
+    Some(self.values.remove(value_index))
+  }
+  /// Swaps the values stored at `lhs` and `rhs`.
+  ///
+  /// # Panics
+  ///
+  /// * If `lhs` or `rhs` is unset.
+  #[track_caller]
+  pub fn swap(&mut self, lhs: Idx, rhs: Idx) {
+    let lhs = self.indices.binary_search(&lhs).expect("swapped an empty index");
+    let rhs = self.indices.binary_search(&rhs).expect("swapped an empty index");
 
-impl<T1,Alloc1,T2,Alloc2> PartialEq<SparseVec<T2,Alloc2>> for SparseVec<T1,Alloc1>
-  where T1: PartialEq<T2>, Alloc1: Allocator, Alloc2: Allocator {
-  fn eq(&self, rhs: &SparseVec<T2,Alloc2>) -> bool {
-    self.indices == rhs.indices && self.values == rhs.values
+    self.values.swap(lhs,rhs)
+  }
+  /// Replaces the value at `index` with `f(value)`, unsetting `index` if `f` returns `None`.
+  ///
+  /// Returns `true` if `index` holds a value afterwards.
+  ///
+  /// If `f` panics `index` is unset.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to alter.  
+  /// f --- Maps the value at `index`, if any, to its new value.  
+  pub fn alter<F>(&mut self, index: Idx, f: F) -> bool
+    where F: FnOnce(Option<T>) -> Option<T> {
+    /// Removes the moved out value at `position` unless forgotten.
+    struct Guard<'a, T, Alloc, Idx>
+      where Alloc: Allocator {
+      /// SparseVec being altered.
+      sparse_vec: &'a mut SparseVec<T, Alloc, Idx>,
+      /// Position of the moved out value.
+      position: usize,
+    }
+
+    impl<T, Alloc, Idx> Drop for Guard<'_, T, Alloc, Idx>
+      where Alloc: Allocator {
+      fn drop(&mut self) {
+        let values = &mut self.sparse_vec.values;
+        let len = values.len();
+
+        self.sparse_vec.indices.remove(self.position);
+        unsafe {
+          let value = values.as_mut_ptr().add(self.position);
+
+          ptr::copy(value.add(1),value,len - self.position - 1);
+          values.set_len(len - 1);
+        }
+      }
+    }
+
+    match self.indices.binary_search(&index) {
+      Ok(position) => {
+        let value = unsafe { ptr::read(self.values.as_ptr().add(position)) };
+        let guard = Guard{sparse_vec: self,position};
+        let Some(value) = f(Some(value)) else { return false };
+
+        unsafe { ptr::write(guard.sparse_vec.values.as_mut_ptr().add(position),value) }
+        mem::forget(guard);
+
+        true
+      },
+      Err(position) => {
+        let Some(value) = f(None) else { return false };
+
+        self.indices.insert(position,index);
+        self.values.insert(position,value);
+
+        true
+      },
+    }
+  }
+  /// Moves the value stored at `from` to `to` and returns any value previously stored at `to`.
+  ///
+  /// Only the values between `from` and `to` are shifted when `to` is unset.
+  ///
+  /// # Params
+  ///
+  /// from --- External index to move the value from.  
+  /// to --- External index to move the value to.  
+  ///
+  /// # Errors
+  ///
+  /// * If `from` is unset.
+  pub fn move_entry(&mut self, from: Idx, to: Idx) -> Result<Option<T>, MoveError<Idx>> {
+    let source = self.indices.binary_search(&from).map_err(|_| MoveError{from})?;
+
+    match self.indices.binary_search(&to) {
+      Ok(target) if target == source => Ok(None),
+      Ok(target) => {
+        self.values.swap(source,target);
+        self.indices.remove(source);
+
+        Ok(Some(self.values.remove(source)))
+      },
+      Err(target) if source < target => {
+        self.indices[source..target].rotate_left(1);
+        self.values[source..target].rotate_left(1);
+        self.indices[target - 1] = to;
+
+        Ok(None)
+      },
+      Err(target) => {
+        self.indices[target..=source].rotate_right(1);
+        self.values[target..=source].rotate_right(1);
+        self.indices[target] = to;
+
+        Ok(None)
+      },
+    }
+  }
+  /// Replaces every set index with `f(index)`, keeping each value with its index.
+  ///
+  /// This is `O(n log n)` as the indices are re-sorted. Leaves `self` unchanged on error.
+  ///
+  /// # Params
+  ///
+  /// f --- Maps each index to its new index.  
+  ///
+  /// # Errors
+  ///
+  /// * If `f` maps two indices to the same index.
+  pub fn remap_indices<F>(&mut self, mut f: F) -> Result<(), DuplicateIndexError<Idx>>
+    where Alloc: Clone, F: FnMut(Idx) -> Idx {
+    let allocator = self.indices.allocator().clone();
+    let mut remapped = Vec::with_capacity_in(self.count(),allocator.clone());
+    let mut order = Vec::with_capacity_in(self.count(),allocator);
+
+    remapped.extend(self.indices.iter().map(|&index| f(index)));
+    order.extend(0..self.count());
+    order.sort_unstable_by_key(|&position| remapped[position]);
+    if let Some(pair) = order.windows(2).find(|pair| remapped[pair[0]] == remapped[pair[1]]) {
+      return Err(DuplicateIndexError{index: remapped[pair[0]]})
+    }
+
+    for (index,&position) in self.indices.iter_mut().zip(&order) { *index = remapped[position] }
+    // Moves each value into place by following the cycles of `order`.
+    for start in 0..order.len() {
+      let mut current = start;
+
+      while order[current] != start {
+        let next = order[current];
+
+        self.values.swap(current,next);
+        order[current] = current;
+        current = next;
+      }
+      order[current] = current;
+    }
+
+    Ok(())
+  }
+  /// Replaces every set index with `f(index)`, keeping each value with its index.
+  ///
+  /// This is `O(n)` as `f` must be strictly increasing.
+  ///
+  /// # Params
+  ///
+  /// f --- Maps each index to its new index.  
+  ///
+  /// # Panics
+  ///
+  /// * If `f` is not strictly increasing, clearing `self`.
+  #[track_caller]
+  pub fn remap_indices_monotonic<F>(&mut self, mut f: F)
+    where F: FnMut(Idx) -> Idx {
+    let guard = ClearGuard(self);
+    let mut previous = None;
+
+    for index in &mut guard.0.indices {
+      let remapped = f(*index);
+
+      if previous.is_some_and(|previous| previous >= remapped) {
+        panic!("remapped indices were not strictly increasing")
+      }
+
+      *index = remapped;
+      previous = Some(remapped);
+    }
+    mem::forget(guard);
+  }
+  /// Unsets all indices.
+  ///
+  /// Retains the allocated capacity.
+  pub fn clear(&mut self) {
+    self.indices.clear();
+    self.values.clear();
+  }
+  /// Unsets all indices, yielding the removed `(index, value)` pairs in order.
+  ///
+  /// Retains the allocated capacity. Any pairs not yielded are dropped with the iterator.
+  pub fn drain(&mut self) -> Drain<'_, T, Alloc, Idx> {
+    Drain{indices: self.indices.drain(..),values: self.values.drain(..)}
+  }
+  /// Unsets all indices in `range`, yielding the removed `(index, value)` pairs in order.
+  ///
+  /// Any pairs not yielded are dropped with the iterator.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to unset.  
+  pub fn drain_range<R>(&mut self, range: R) -> Drain<'_, T, Alloc, Idx>
+    where R: RangeBounds<Idx> {
+    let range = self.position_range(range);
+
+    Drain{indices: self.indices.drain(range.clone()),values: self.values.drain(range)}
+  }
+  /// Unsets all indices in `range` and returns the count of values removed.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to unset.  
+  pub fn remove_range<R>(&mut self, range: R) -> usize
+    where R: RangeBounds<Idx> {
+    let range = self.position_range(range);
+
+    self.indices.drain(range.clone());
+    self.values.drain(range.clone());
+    range.len()
+  }
+  /// Unsets all indices in `range` and returns the removed values.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to unset.  
+  pub fn remove_range_collect<R>(&mut self, range: R) -> Self
+    where R: RangeBounds<Idx>, Alloc: Clone {
+    let range = self.position_range(range);
+    let mut removed = Self::with_capacity_in(range.len(),self.indices.allocator().clone());
+
+    removed.indices.extend(self.indices.drain(range.clone()));
+    removed.values.extend(self.values.drain(range));
+    removed
+  }
+  /// Takes the contents of `self` leaving an empty SparseVec in the same allocator.
+  pub fn take(&mut self) -> Self
+    where Alloc: Clone {
+    let empty = Self::new_in(self.indices.allocator().clone());
+
+    self.replace(empty)
+  }
+  /// Replaces the contents of `self` with `other` and returns the previous contents.
+  ///
+  /// # Params
+  ///
+  /// other --- New contents of `self`.  
+  pub fn replace(&mut self, other: Self) -> Self { mem::replace(self,other) }
+  /// Unsets all indices greater than or equal to `bound`.
+  ///
+  /// Does not reallocate.
+  pub fn truncate_from(&mut self, bound: Idx) {
+    let len = self.indices.partition_point(|&index| index < bound);
+
+    self.indices.truncate(len);
+    self.values.truncate(len);
+  }
+  /// Splits off all indices greater than or equal to `bound` into a new SparseVec.
+  ///
+  /// # Params
+  ///
+  /// bound --- Lowest external index to split off.  
+  pub fn split_off(&mut self, bound: Idx) -> Self
+    where Alloc: Clone {
+    let at = self.indices.partition_point(|&index| index < bound);
+    let indices = self.indices.split_off(at);
+    let values = self.values.split_off(at);
+
+    unsafe { Self::from_parts(indices,values) }
+  }
+  /// Moves all values from `other` into `self`, leaving `other` empty.
+  ///
+  /// Values from `other` replace values at the same index in `self`.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to take values from.  
+  pub fn append(&mut self, other: &mut Self)
+    where Alloc: Clone {
+    self.append_with(other,|_,_,value| value)
+  }
+  /// Moves all values from `other` into `self`, leaving `other` empty.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to take values from.  
+  /// resolve --- Combines `(index, ours, theirs)` for indices set in both.  
+  pub fn append_with<F>(&mut self, other: &mut Self, resolve: F)
+    where Alloc: Clone, F: FnMut(Idx, T, T) -> T {
+    match (self.indices.last(),other.indices.first()) {
+      (_,None) => (),
+      (Some(last),Some(first)) if last >= first => self.merge_sorted(other.drain(),resolve),
+      _ => {
+        self.indices.append(&mut other.indices);
+        self.values.append(&mut other.values);
+      },
+    }
+  }
+  /// Moves all values from `other` into `self`.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to take values from.  
+  /// combine --- Folds `other`'s value into `self`'s value for indices set in both.  
+  pub fn merge_with<F>(&mut self, other: Self, mut combine: F)
+    where Alloc: Clone, F: FnMut(&mut T, T) {
+    self.merge_sorted(other,|_,mut lhs,rhs| {
+      combine(&mut lhs,rhs);
+      lhs
+    })
+  }
+  /// Unsets the lowest set index and returns it with its value.
+  ///
+  /// This is `O(n)` as all remaining values are shifted down.
+  pub fn pop_first(&mut self) -> Option<(Idx, T)> {
+    if self.is_empty() { return None }
+
+    Some((self.indices.remove(0),self.values.remove(0)))
+  }
+  /// Unsets the highest set index and returns it with its value.
+  pub fn pop_last(&mut self) -> Option<(Idx, T)> {
+    Some((self.indices.pop()?,self.values.pop()?))
+  }
+  /// Unsets all indices for which `f` returns `false`.
+  ///
+  /// Visits each set index once in order.
+  ///
+  /// # Params
+  ///
+  /// f --- Tests if `(index, value)` should be kept.  
+  pub fn retain<F>(&mut self, mut f: F)
+    where F: FnMut(Idx, &mut T) -> bool {
+    /// Compacts the retained values when dropped, even if `f` panics.
+    struct Guard<'a, T, Alloc, Idx>
+      where Alloc: Allocator {
+      /// SparseVec being filtered.
+      sparse_vec: &'a mut SparseVec<T, Alloc, Idx>,
+      /// Count of values kept at the front.
+      kept: usize,
+      /// Count of values visited.
+      processed: usize,
+    }
+
+    impl<T, Alloc, Idx> Drop for Guard<'_, T, Alloc, Idx>
+      where Alloc: Allocator {
+      fn drop(&mut self) {
+        self.sparse_vec.indices.drain(self.kept..self.processed);
+        self.sparse_vec.values.drain(self.kept..self.processed);
+      }
+    }
+
+    let mut guard = Guard{sparse_vec: self,kept: 0,processed: 0};
+
+    while guard.processed < guard.sparse_vec.count() {
+      let position = guard.processed;
+      let index = guard.sparse_vec.indices[position];
+      let keep = f(index,&mut guard.sparse_vec.values[position]);
+
+      guard.processed += 1;
+      if keep {
+        guard.sparse_vec.indices.swap(guard.kept,position);
+        guard.sparse_vec.values.swap(guard.kept,position);
+        guard.kept += 1;
+      }
+    }
+  }
+  /// Locates the slot of `index` by exponential search outwards from `hint`.
+  ///
+  /// Returns the same result as a binary search over `indices`.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to locate.  
+  /// hint --- Slot to start searching from.  
+  fn search_with_hint(&self, index: Idx, hint: usize) -> Result<usize, usize> {
+    let indices = self.indices.as_slice();
+    let len = indices.len();
+    let hint = hint.min(len);
+    // `indices[..start]` is less than `index` and `indices[end..]` is greater or equal.
+    let (start,end) = if indices.get(hint).is_some_and(|&other| other < index) {
+      let (mut lower,mut step) = (hint,1);
+      let upper = loop {
+        let Some(probe) = lower.checked_add(step).filter(|&probe| probe < len) else { break len };
+        if indices[probe] >= index { break probe }
+
+        lower = probe;
+        step *= 2;
+      };
+
+      (lower + 1,upper)
+    } else {
+      let (mut upper,mut step) = (hint,1);
+      let lower = loop {
+        let Some(probe) = upper.checked_sub(step) else { break 0 };
+        if indices[probe] < index { break probe + 1 }
+
+        upper = probe;
+        step *= 2;
+      };
+
+      (lower,upper)
+    };
+
+    match indices[start..len.min(end + 1)].binary_search(&index) {
+      Ok(slot) => Ok(start + slot),
+      Err(slot) => Err(start + slot),
+    }
+  }
+  /// Returns the positions in `values` of the indices in `range`.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to locate.  
+  fn position_range<R>(&self, range: R) -> Range<usize>
+    where R: RangeBounds<Idx> {
+    let start = match range.start_bound() {
+      Bound::Included(&start) => self.indices.partition_point(|&index| index < start),
+      Bound::Excluded(&start) => self.indices.partition_point(|&index| index <= start),
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(&end) => self.indices.partition_point(|&index| index <= end),
+      Bound::Excluded(&end) => self.indices.partition_point(|&index| index < end),
+      Bound::Unbounded => self.count(),
+    };
+
+    start..end.max(start)
+  }
+  /// Unsets all indices for which `f` returns `true`, yielding the removed `(index, value)` pairs in order.
+  ///
+  /// Any pairs not yet visited are kept when the iterator is dropped.
+  ///
+  /// # Params
+  ///
+  /// f --- Tests if `(index, value)` should be extracted.  
+  pub fn extract_if<F>(&mut self, f: F) -> ExtractIf<'_, T, Alloc, F, Idx>
+    where F: FnMut(Idx, &mut T) -> bool {
+    ExtractIf::new(self,f)
+  }
+  /// Collects `(index, value)` pairs in any order, combining the values of repeated indices.
+  ///
+  /// # Params
+  ///
+  /// iter --- `(index, value)` pairs to collect.  
+  /// allocator --- Constructs each allocation.  
+  /// merge --- Combines the earlier and later values of a repeated index.  
+  fn collect_unsorted<I, A, F>(iter: I, mut allocator: A, mut merge: F) -> Self
+    where I: IntoIterator<Item = (Idx, T)>, A: FnMut() -> Alloc, F: FnMut(Idx, T, T) -> T {
+    let mut pairs = Vec::new_in(allocator());
+
+    pairs.extend(iter);
+    // Stable so that repeated indices remain in insertion order.
+    pairs.sort_by_key(|&(index,_)| index);
+
+    let mut indices = Vec::with_capacity_in(pairs.len(),allocator());
+    let mut values = Vec::with_capacity_in(pairs.len(),allocator());
+
+    for (index,value) in pairs {
+      if indices.last() == Some(&index) {
+        let earlier = values.pop().unwrap();
+
+        values.push(merge(index,earlier,value));
+      } else {
+        indices.push(index);
+        values.push(value);
+      }
+    }
+
+    unsafe { Self::from_parts(indices,values) }
+  }
+  /// Merges sorted `(index, value)` pairs with unique indices into `self`.
+  ///
+  /// # Params
+  ///
+  /// other --- Pairs to merge, sorted by unique index.  
+  /// resolve --- Combines `(index, ours, theirs)` for indices set in both.  
+  fn merge_sorted<I, F>(&mut self, other: I, mut resolve: F)
+    where Alloc: Clone, I: IntoIterator<Item = (Idx, T)>, F: FnMut(Idx, T, T) -> T {
+    let mut other = other.into_iter().peekable();
+    let Some(&(first,_)) = other.peek() else { return };
+
+    if self.indices.last().is_none_or(|&last| last < first) {
+      for (index,value) in other {
+        self.indices.push(index);
+        self.values.push(value);
+      }
+
+      return
+    }
+
+    let capacity = self.count() + other.size_hint().0;
+    let allocator = self.indices.allocator().clone();
+    let mut ours = self.replace(Self::with_capacity_in(capacity,allocator)).into_iter().peekable();
+
+    loop {
+      let order = match (ours.peek(),other.peek()) {
+        (None,None) => break,
+        (Some(_),None) => Ordering::Less,
+        (None,Some(_)) => Ordering::Greater,
+        (Some(&(lhs,_)),Some(&(rhs,_))) => lhs.cmp(&rhs),
+      };
+      let (index,value) = match order {
+        Ordering::Less => ours.next().unwrap(),
+        Ordering::Greater => other.next().unwrap(),
+        Ordering::Equal => {
+          let (index,lhs) = ours.next().unwrap();
+          let (_,rhs) = other.next().unwrap();
+
+          (index,resolve(index,lhs,rhs))
+        },
+      };
+
+      self.indices.push(index);
+      self.values.push(value);
+    }
+  }
+  /// Gets the [Entry] at `index` for in-place manipulation.
+  pub fn entry(&mut self, index: Idx) -> Entry<'_, T, Alloc, Idx> {
+    match self.indices.binary_search(&index) {
+      Ok(value_index) => Entry::Occupied(OccupiedEntry{sparse_vec: self,value_index}),
+      Err(value_index) => Entry::Vacant(VacantEntry{sparse_vec: self,index,value_index}),
+    }
+  }
+  /// Iterates over all set indices.
+  pub fn iter(&self) -> impl Iterator<Item = (Idx, &T)> + Clone {
+    self.indices.iter().copied().zip(self.values.iter())
+  }
+  /// Iterates over all set indices.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (Idx, &mut T)> {
+    self.indices.iter().copied().zip(self.values.iter_mut())
+  }
+  /// Constructs a SparseVec with the same indices and the values mapped by `f`.
+  ///
+  /// # Params
+  ///
+  /// f --- Maps each value.  
+  pub fn map_values<U, F>(&self, f: F) -> SparseVec<U, Alloc, Idx>
+    where Alloc: Clone, F: FnMut(&T) -> U {
+    let mut values = Vec::with_capacity_in(self.count(),self.values.allocator().clone());
+
+    values.extend(self.values.iter().map(f));
+    unsafe { SparseVec::from_parts(self.indices.clone(),values) }
+  }
+  /// Calls `f` on every set index and its value in order.
+  ///
+  /// # Params
+  ///
+  /// f --- Modifies each value.  
+  pub fn map_values_mut<F>(&mut self, mut f: F)
+    where F: FnMut(Idx, &mut T) {
+    for (index,value) in self.iter_mut() { f(index,value) }
+  }
+  /// Iterates over the set indices in `range`.
+  ///
+  /// An inverted range yields nothing.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to iterate over.  
+  pub fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = (Idx, &T)> + ExactSizeIterator + Clone
+    where R: RangeBounds<Idx> {
+    let range = self.position_range(range);
+
+    self.indices[range.clone()].iter().copied().zip(self.values[range].iter())
+  }
+  /// Iterates over the set indices in `range`.
+  ///
+  /// An inverted range yields nothing.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to iterate over.  
+  pub fn range_mut<R>(&mut self, range: R) -> impl DoubleEndedIterator<Item = (Idx, &mut T)> + ExactSizeIterator
+    where R: RangeBounds<Idx> {
+    let range = self.position_range(range);
+
+    self.indices[range.clone()].iter().copied().zip(self.values[range].iter_mut())
+  }
+  /// Iterates over each pair of consecutive set indices and their values.
+  pub fn iter_adjacent_pairs(&self) -> impl ExactSizeIterator<Item = ((Idx, &T), (Idx, &T))> + Clone {
+    self.indices.windows(2).zip(self.values.windows(2))
+      .map(|(indices,values)| ((indices[0],&values[0]),(indices[1],&values[1])))
+  }
+  /// Calls `f` on each pair of consecutive set indices and their values in order.
+  ///
+  /// Each value is borrowed by two consecutive calls so this cannot be an iterator.
+  ///
+  /// # Params
+  ///
+  /// f --- Called with `(lower index, lower value, upper index, upper value)`.  
+  pub fn for_each_adjacent_pair_mut<F>(&mut self, mut f: F)
+    where F: FnMut(Idx, &mut T, Idx, &mut T) {
+    for position in 1..self.count() {
+      let (lower,upper) = self.values.split_at_mut(position);
+
+      f(self.indices[position - 1],&mut lower[position - 1],self.indices[position],&mut upper[0])
+    }
+  }
+  /// Iterates over every index set in either `self` or `other` in ascending order.
+  ///
+  /// Yields `(index, self value, other value)` with `None` for the side where `index` is unset.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to merge with.  
+  pub fn union<'a, T2, Alloc2>(&'a self, other: &'a SparseVec<T2, Alloc2, Idx>) -> impl Iterator<Item = (Idx, Option<&'a T>, Option<&'a T2>)> + Clone
+    where Alloc2: Allocator {
+    let mut lhs = self.iter().peekable();
+    let mut rhs = other.iter().peekable();
+
+    iter::from_fn(move || {
+      let order = match (lhs.peek(),rhs.peek()) {
+        (Some((lhs_index,_)),Some((rhs_index,_))) => lhs_index.cmp(rhs_index),
+        (Some(_),None) => Ordering::Less,
+        (None,Some(_)) => Ordering::Greater,
+        (None,None) => return None,
+      };
+
+      match order {
+        Ordering::Less => lhs.next().map(|(index,value)| (index,Some(value),None)),
+        Ordering::Greater => rhs.next().map(|(index,value)| (index,None,Some(value))),
+        Ordering::Equal => {
+          let (index,lhs_value) = lhs.next()?;
+          let (_,rhs_value) = rhs.next()?;
+
+          Some((index,Some(lhs_value),Some(rhs_value)))
+        },
+      }
+    })
+  }
+  /// Iterates over every index set in both `self` and `other` in ascending order.
+  ///
+  /// Yields `(index, self value, other value)`.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to intersect with.  
+  pub fn intersection<'a, T2, Alloc2>(&'a self, other: &'a SparseVec<T2, Alloc2, Idx>) -> impl Iterator<Item = (Idx, &'a T, &'a T2)> + Clone
+    where Alloc2: Allocator {
+    let mut lhs = self.iter().peekable();
+    let mut rhs = other.iter().peekable();
+
+    iter::from_fn(move || loop {
+      let (&(lhs_index,_),&(rhs_index,_)) = (lhs.peek()?,rhs.peek()?);
+
+      match lhs_index.cmp(&rhs_index) {
+        Ordering::Less => { lhs.next(); },
+        Ordering::Greater => { rhs.next(); },
+        Ordering::Equal => {
+          let (index,lhs_value) = lhs.next()?;
+          let (_,rhs_value) = rhs.next()?;
+
+          return Some((index,lhs_value,rhs_value))
+        },
+      }
+    })
+  }
+  /// Iterates over every index set in `self` but unset in `other` in ascending order.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec whose indices are excluded.  
+  pub fn difference<'a, T2, Alloc2>(&'a self, other: &'a SparseVec<T2, Alloc2, Idx>) -> impl Iterator<Item = (Idx, &'a T)> + Clone
+    where Alloc2: Allocator {
+    let mut rhs = other.indices.iter().peekable();
+
+    self.iter().filter(move |&(index,_)| {
+      while rhs.next_if(|&&other| other < index).is_some() {}
+
+      rhs.peek() != Some(&&index)
+    })
+  }
+  /// Iterates over all set indices in ascending order.
+  pub fn indices(&self) -> impl Iterator<Item = Idx> + Clone { self.indices.iter().copied() }
+  /// Iterates over all stored values in index order.
+  pub fn values(&self) -> impl Iterator<Item = &T> + Clone { self.values.iter() }
+  /// Iterates over all stored values in index order.
+  pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> { self.values.iter_mut() }
+}
+
+impl<T, Alloc> SparseVec<T, Alloc>
+  where Alloc: Allocator {
+  /// Constructs a SparseVec from a dense sequence, storing each `Some` value at its position.
+  ///
+  /// # Params
+  ///
+  /// iter --- Dense sequence of values.  
+  pub fn from_dense<I>(iter: I) -> Self
+    where Alloc: Default, I: IntoIterator<Item = Option<T>> {
+    let mut sparse_vec = Self::new();
+
+    for (index,value) in iter.into_iter().enumerate() {
+      let Some(value) = value else { continue };
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value);
+    }
+
+    sparse_vec
+  }
+  /// Constructs a SparseVec from a dense slice, storing a clone of each `Some` value at its
+  /// position.
+  ///
+  /// # Params
+  ///
+  /// dense --- Dense sequence of values.  
+  pub fn from_dense_options(dense: &[Option<T>]) -> Self
+    where T: Clone, Alloc: Default {
+    let mut sparse_vec = Self::with_capacity(dense.iter().flatten().count());
+
+    for (index,value) in dense.iter().enumerate() {
+      let Some(value) = value else { continue };
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value.clone());
+    }
+
+    sparse_vec
+  }
+  /// Constructs a SparseVec from a dense slice, storing a clone of each value kept by `keep` at
+  /// its position.
+  ///
+  /// `keep` is called twice for each value so that the SparseVec is allocated once.
+  ///
+  /// # Params
+  ///
+  /// dense --- Dense sequence of values.  
+  /// keep --- Tests if a value should be stored.  
+  /// allocator --- Allocator of the SparseVec.  
+  pub fn from_dense_filter<F>(dense: &[T], mut keep: F, allocator: Alloc) -> Self
+    where T: Clone, Alloc: Clone, F: FnMut(&T) -> bool {
+    let mut sparse_vec = Self::with_capacity_in(dense.iter().filter(|value| keep(value)).count(),allocator);
+
+    for (index,value) in dense.iter().enumerate() {
+      if !keep(value) { continue }
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value.clone());
+    }
+
+    sparse_vec
+  }
+  /// Constructs a SparseVec from a dense slice, storing a clone of each value which is not
+  /// `T::default()` at its position.
+  ///
+  /// # Params
+  ///
+  /// dense --- Dense sequence of values.  
+  /// allocator --- Allocator of the SparseVec.  
+  pub fn from_dense_nonzero(dense: &[T], allocator: Alloc) -> Self
+    where T: Clone + Default + PartialEq, Alloc: Clone {
+    let zero = T::default();
+
+    Self::from_dense_filter(dense,|value| *value != zero,allocator)
+  }
+  /// Tests if every index in `range` holds a value.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to test.  
+  pub fn contains_range<R>(&self, range: R) -> bool
+    where R: RangeBounds<usize> {
+    let Some(range) = Self::inclusive_range(range) else { return true };
+    let len = (range.end() - range.start()).checked_add(1);
+
+    len == Some(self.count_in_range(range))
+  }
+  /// Returns the set index closest to `probe`.
+  ///
+  /// If two set indices are equally close the lower index is returned.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub fn nearest_set_index(&self, probe: usize) -> Option<usize> {
+    self.nearest_entry(probe).map(|(index,_)| index)
+  }
+  /// Gets the set index closest to `probe` and its value.
+  ///
+  /// If two set indices are equally close the lower index is returned.
+  ///
+  /// Returns `None` if `self` is empty.
+  pub fn nearest_entry(&self, probe: usize) -> Option<(usize, &T)> {
+    let slot = self.lower_bound(probe);
+    let prev = slot.checked_sub(1).and_then(|slot| self.nth(slot));
+
+    match (prev,self.nth(slot)) {
+      (Some(prev),Some(next)) => Some(if probe - prev.0 <= next.0 - probe { prev } else { next }),
+      (prev,next) => prev.or(next),
+    }
+  }
+  /// Returns the set index closest to `probe`, if it is within `max_distance` of `probe`.
+  ///
+  /// If two set indices are equally close the lower index is returned.
+  ///
+  /// Returns `None` if no index within `max_distance` is set.
+  pub fn nearest_set_index_within(&self, probe: usize, max_distance: usize) -> Option<usize> {
+    self.nearest_entry_within(probe,max_distance).map(|(index,_)| index)
+  }
+  /// Gets the set index closest to `probe` and its value, if it is within `max_distance` of `probe`.
+  ///
+  /// If two set indices are equally close the lower index is returned.
+  ///
+  /// Returns `None` if no index within `max_distance` is set.
+  pub fn nearest_entry_within(&self, probe: usize, max_distance: usize) -> Option<(usize, &T)> {
+    self.nearest_entry(probe).filter(|&(index,_)| index.abs_diff(probe) <= max_distance)
+  }
+  /// Stores a clone of `value` at every index in `range`.
+  ///
+  /// Replaces any values already stored in `range`.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to store `value` at.  
+  /// value --- Value to store.  
+  ///
+  /// # Panics
+  ///
+  /// * If `range` holds more indices than can be stored, such as a range with an unbounded end.
+  ///   `self` is left unmodified.
+  /// * If cloning `value` panics, clearing `self`.
+  #[track_caller]
+  pub fn fill_range<R>(&mut self, range: R, value: T)
+    where R: RangeBounds<usize>, T: Clone {
+    let Some(range) = Self::inclusive_range(range) else { return };
+    let count = (range.end() - range.start()).checked_add(1).expect("filled more indices than can be stored");
+
+    // Reserving first panics on an oversized range before `self` is modified.
+    self.reserve(count - self.count_in_range(range.clone()));
+    self.splice_range(range,iter::repeat_n(value,count))
+  }
+  /// Stores a clone of each value in `block` at consecutive indices starting at `base`.
+  ///
+  /// Replaces any values already stored in the block.
+  ///
+  /// # Params
+  ///
+  /// base --- External index to store the first value at.  
+  /// block --- Values to store.  
+  ///
+  /// # Panics
+  ///
+  /// * If the last index of the block overflows `usize`. `self` is left unmodified.
+  /// * If cloning a value of `block` panics, clearing `self`.
+  #[track_caller]
+  pub fn set_slice(&mut self, base: usize, block: &[T])
+    where T: Clone {
+    let Some(len) = block.len().checked_sub(1) else { return };
+    let end = base.checked_add(len).expect("block overflowed the index range");
+
+    self.splice_range(base..=end,block.iter().cloned())
+  }
+  /// Shifts all indices greater than or equal to `index` up by one and stores `value` at `index`.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to open.  
+  /// value --- Value to store at `index`, if any.  
+  ///
+  /// # Panics
+  ///
+  /// * If the highest index overflows `usize`.
+  #[track_caller]
+  pub fn insert_shift(&mut self, index: usize, value: Option<T>) {
+    let position = self.shift_up(index,1);
+
+    if let Some(value) = value {
+      self.indices.insert(position,index);
+      self.values.insert(position,value);
+    }
+  }
+  /// Shifts all indices greater than or equal to `index` up by `count`.
+  ///
+  /// # Params
+  ///
+  /// index --- First external index to open.  
+  /// count --- Count of indices to open.  
+  ///
+  /// # Panics
+  ///
+  /// * If the highest index overflows `usize`.
+  #[track_caller]
+  pub fn insert_shift_n(&mut self, index: usize, count: usize) { self.shift_up(index,count); }
+  /// Unsets `index`, shifts all greater indices down by one and returns any previously stored value.
+  ///
+  /// # Params
+  ///
+  /// index --- External index to close.  
+  pub fn remove_shift(&mut self, index: usize) -> Option<T> {
+    let position = self.indices.partition_point(|&other| other < index);
+    let value = match self.indices.get(position) {
+      Some(&other) if other == index => {
+        self.indices.remove(position);
+        Some(self.values.remove(position))
+      },
+      _ => None,
+    };
+
+    for other in &mut self.indices[position..] { *other -= 1 }
+
+    value
+  }
+  /// Unsets all indices in `range`, shifts all greater indices down by the length of `range` and
+  /// returns the count of values removed.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to close.  
+  pub fn remove_shift_range<R>(&mut self, range: R) -> usize
+    where R: RangeBounds<usize> {
+    let Some(range) = Self::inclusive_range(range) else { return 0 };
+    let (start,end) = (*range.start(),*range.end());
+    let positions = self.position_range(range);
+
+    self.indices.drain(positions.clone());
+    self.values.drain(positions.clone());
+    for other in &mut self.indices[positions.start..] { *other = *other - end - 1 + start }
+
+    positions.len()
+  }
+  /// Adds `delta` to every set index.
+  ///
+  /// Leaves `self` unchanged on error.
+  ///
+  /// # Params
+  ///
+  /// delta --- Offset to add to every index.  
+  ///
+  /// # Errors
+  ///
+  /// * If any index would leave the range of `usize`.
+  pub fn offset_indices(&mut self, delta: isize) -> Result<(), OffsetError> {
+    let (Some(&first),Some(&last)) = (self.indices.first(),self.indices.last()) else { return Ok(()) };
+
+    if delta < 0 {
+      first.checked_sub(delta.unsigned_abs()).ok_or(OffsetError::Underflow)?;
+    } else {
+      last.checked_add(delta.unsigned_abs()).ok_or(OffsetError::Overflow)?;
+    }
+    for index in &mut self.indices { *index = index.wrapping_add_signed(delta) }
+
+    Ok(())
+  }
+  /// Shifts all indices greater than or equal to `index` up by `count`.
+  ///
+  /// Returns the position in `values` of the first shifted index.
+  ///
+  /// # Params
+  ///
+  /// index --- First external index to shift.  
+  /// count --- Distance to shift.  
+  #[track_caller]
+  fn shift_up(&mut self, index: usize, count: usize) -> usize {
+    let position = self.indices.partition_point(|&other| other < index);
+
+    if self.indices[position..].last().is_some_and(|last| last.checked_add(count).is_none()) {
+      panic!("shifted an index past `usize::MAX`")
+    }
+    for other in &mut self.indices[position..] { *other += count }
+
+    position
+  }
+  /// Converts `range` into an equivalent inclusive range.
+  ///
+  /// Returns `None` if `range` is empty.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to convert.  
+  fn inclusive_range<R>(range: R) -> Option<RangeInclusive<usize>>
+    where R: RangeBounds<usize> {
+    let start = match range.start_bound() {
+      Bound::Included(&start) => start,
+      Bound::Excluded(&start) => start.checked_add(1)?,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(&end) => end,
+      Bound::Excluded(&end) => end.checked_sub(1)?,
+      Bound::Unbounded => usize::MAX,
+    };
+
+    if start <= end { Some(start..=end) } else { None }
+  }
+  /// Stores `values` at every index in `range`, replacing any values already stored in `range`.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to store `values` at.  
+  /// values --- Values to store, one per index in `range`.  
+  fn splice_range<I>(&mut self, range: RangeInclusive<usize>, values: I)
+    where I: IntoIterator<Item = T> {
+    let positions = self.position_range(range.clone());
+    let guard = ClearGuard(self);
+
+    guard.0.values.splice(positions.clone(),values);
+    guard.0.indices.splice(positions,range);
+    mem::forget(guard);
+  }
+  /// Constructs a dense Vec of length `len` holding a clone of each value below `len`.
+  ///
+  /// Unset indices hold `None`. Indices greater than or equal to `len` are skipped.
+  ///
+  /// # Params
+  ///
+  /// len --- Length of the dense Vec.  
+  pub fn to_dense(&self, len: usize) -> Vec<Option<T>, Alloc>
+    where T: Clone, Alloc: Clone {
+    let mut dense = Vec::with_capacity_in(len,self.values.allocator().clone());
+
+    dense.resize_with(len,|| None);
+    for (index,value) in self.range(..len) { dense[index] = Some(value.clone()) }
+
+    dense
+  }
+  /// Converts `self` into a dense Vec of length `len`, moving each value to its index.
+  ///
+  /// Unset indices hold `None`.
+  ///
+  /// # Params
+  ///
+  /// len --- Length of the dense Vec.  
+  ///
+  /// # Errors
+  ///
+  /// * If any index is greater than or equal to `len`. `self` is returned unchanged.
+  pub fn into_dense(self, len: usize) -> Result<Vec<Option<T>, Alloc>, Self>
+    where Alloc: Clone {
+    if self.last_index().is_some_and(|last| last >= len) { return Err(self) }
+
+    let mut dense = Vec::with_capacity_in(len,self.values.allocator().clone());
+
+    dense.resize_with(len,|| None);
+    for (index,value) in self { dense[index] = Some(value) }
+
+    Ok(dense)
+  }
+  /// Constructs a dense Vec of length `len` holding a clone of each value below `len`.
+  ///
+  /// Unset indices hold a clone of `fill`. Indices greater than or equal to `len` are skipped.
+  ///
+  /// # Params
+  ///
+  /// len --- Length of the dense Vec.  
+  /// fill --- Value of the unset indices.  
+  pub fn to_dense_filled(&self, len: usize, fill: T) -> Vec<T, Alloc>
+    where T: Clone, Alloc: Clone {
+    let mut dense = Vec::with_capacity_in(len,self.values.allocator().clone());
+
+    dense.resize(len,fill);
+    for (index,value) in self.range(..len) { dense[index] = value.clone() }
+
+    dense
+  }
+  /// Converts `self` into a dense Vec of length `len`, moving each value to its index.
+  ///
+  /// Unset indices hold a clone of `fill`.
+  ///
+  /// # Params
+  ///
+  /// len --- Length of the dense Vec.  
+  /// fill --- Value of the unset indices.  
+  ///
+  /// # Errors
+  ///
+  /// * If any index is greater than or equal to `len`. `self` is returned unchanged.
+  pub fn into_dense_filled(self, len: usize, fill: T) -> Result<Vec<T, Alloc>, Self>
+    where T: Clone, Alloc: Clone {
+    if self.last_index().is_some_and(|last| last >= len) { return Err(self) }
+
+    let mut dense = Vec::with_capacity_in(len,self.values.allocator().clone());
+
+    dense.resize(len,fill);
+    for (index,value) in self { dense[index] = value }
+
+    Ok(dense)
+  }
+  /// Clones each value into `dense` at its index, leaving the unset indices untouched.
+  ///
+  /// Values whose index is out of bounds for `dense` are skipped.
+  ///
+  /// Returns the count of skipped values.
+  ///
+  /// # Params
+  ///
+  /// dense --- Dense slice to write into.  
+  pub fn scatter(&self, dense: &mut [T]) -> usize
+    where T: Clone {
+    let positions = self.position_range(..dense.len());
+
+    for (&index,value) in self.indices[positions.clone()].iter().zip(&self.values[positions.clone()]) {
+      dense[index].clone_from(value)
+    }
+
+    self.count() - positions.len()
+  }
+  /// Iterates over every index below `len`, yielding `None` for unset indices.
+  ///
+  /// Indices greater than or equal to `len` are not visited.
+  ///
+  /// # Params
+  ///
+  /// len --- Count of indices to iterate over.  
+  pub fn iter_dense(&self, len: usize) -> impl ExactSizeIterator<Item = Option<&T>> + Clone {
+    let mut set = self.range(..len).peekable();
+
+    (0..len).map(move |index| set.next_if(|&(other,_)| other == index).map(|(_,value)| value))
+  }
+  /// Iterates over every index below `len`, substituting `default` for unset indices.
+  ///
+  /// Indices greater than or equal to `len` are not visited.
+  ///
+  /// # Params
+  ///
+  /// len --- Count of indices to iterate over.  
+  /// default --- Value of unset indices.  
+  pub fn iter_with_default<'a>(&'a self, len: usize, default: &'a T) -> impl ExactSizeIterator<Item = (usize, &'a T)> + Clone {
+    self.iter_dense(len).map(move |value| value.unwrap_or(default)).enumerate()
+  }
+  /// Iterates over a clone of the value at every index below `len`, substituting `default` for
+  /// unset indices.
+  ///
+  /// Indices greater than or equal to `len` are not visited.
+  ///
+  /// # Params
+  ///
+  /// len --- Count of indices to iterate over.  
+  /// default --- Value of unset indices.  
+  pub fn iter_cloned_with_default(&self, len: usize, default: T) -> impl ExactSizeIterator<Item = (usize, T)> + Clone
+    where T: Clone {
+    self.iter_dense(len).map(move |value| value.unwrap_or(&default).clone()).enumerate()
+  }
+  /// Iterates over the unset indices in `range` in ascending order.
+  ///
+  /// An unbounded end iterates up to `usize::MAX`.
+  ///
+  /// # Params
+  ///
+  /// range --- External indices to iterate over.  
+  pub fn iter_unset<R>(&self, range: R) -> impl Iterator<Item = usize> + Clone
+    where R: RangeBounds<usize> {
+    let range = Self::inclusive_range(range);
+    let set = range.clone().map_or(&[][..],|range| &self.indices[self.position_range(range)]);
+    let mut set = set.iter().copied().peekable();
+
+    range.into_iter().flatten().filter(move |&index| {
+      let unset = set.peek() != Some(&index);
+
+      if !unset { set.next(); }
+      unset
+    })
+  }
+}
+
+/// Clears a SparseVec unless forgotten.
+///
+/// Restores the invariants if an operation which temporarily breaks them panics.
+struct ClearGuard<'a, T, Alloc, Idx>(&'a mut SparseVec<T, Alloc, Idx>)
+  where Alloc: Allocator, Idx: Copy + Ord;
+
+impl<T, Alloc, Idx> Drop for ClearGuard<'_, T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  fn drop(&mut self) { self.0.clear() }
+}
+
+impl<T, Alloc, Idx> Clone for SparseVec<T, Alloc, Idx>
+  where T: Clone, Alloc: Allocator + Clone, Idx: Clone {
+  fn clone(&self) -> Self { Self{indices: self.indices.clone(),values: self.values.clone()} }
+  /// Clones `source` into `self`, reusing the allocations of `self`.
+  fn clone_from(&mut self, source: &Self) {
+    self.indices.clone_from(&source.indices);
+    self.values.clone_from(&source.values);
+  }
+}
+
+impl<T,Alloc,Idx> Default for SparseVec<T,Alloc,Idx>
+  where Alloc: Allocator + Default, Idx: Copy + Ord {
+  fn default() -> Self { Self::new() }
+}
+
+impl<T, Alloc> From<Vec<Option<T>, Alloc>> for SparseVec<T, Alloc>
+  where Alloc: Allocator + Clone {
+  /// Moves each `Some` value of a dense Vec to its position.
+  fn from(dense: Vec<Option<T>, Alloc>) -> Self {
+    let mut sparse_vec = Self::with_capacity_in(dense.iter().flatten().count(),dense.allocator().clone());
+
+    for (index,value) in dense.into_iter().enumerate() {
+      let Some(value) = value else { continue };
+
+      sparse_vec.indices.push(index);
+      sparse_vec.values.push(value);
+    }
+
+    sparse_vec
+  }
+}
+
+impl<T, Alloc, Idx> TryFrom<(Vec<Idx, Alloc>, Vec<T, Alloc>)> for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  type Error = FromPartsError<T, Alloc, Idx>;
+
+  /// Constructs a SparseVec from `(indices, values)`, see [SparseVec::try_from_parts].
+  fn try_from((indices,values): (Vec<Idx, Alloc>, Vec<T, Alloc>)) -> Result<Self, Self::Error> {
+    Self::try_from_parts(indices,values)
+  }
+}
+
+impl<T, Alloc, Idx> Extend<(Idx, T)> for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator + Clone, Idx: Copy + Ord {
+  /// Stores `(index, value)` pairs in any order.
+  ///
+  /// If an index is already set or is repeated the last value is kept, matching [SparseVec::set].
+  fn extend<I>(&mut self, iter: I)
+    where I: IntoIterator<Item = (Idx, T)> {
+    let allocator = self.indices.allocator().clone();
+    let pairs = Self::collect_unsorted(iter,|| allocator.clone(),|_,_,value| value);
+
+    self.merge_sorted(pairs,|_,_,value| value);
+  }
+}
+
+impl<'a, T, Alloc, Idx> Extend<(Idx, &'a T)> for SparseVec<T, Alloc, Idx>
+  where T: Copy, Alloc: Allocator + Clone, Idx: Copy + Ord {
+  /// Stores copies of `(index, value)` pairs in any order.
+  ///
+  /// If an index is already set or is repeated the last value is kept, matching [SparseVec::set].
+  fn extend<I>(&mut self, iter: I)
+    where I: IntoIterator<Item = (Idx, &'a T)> {
+    self.extend(iter.into_iter().map(|(index,&value)| (index,value)))
+  }
+}
+
+impl<T, Alloc, Idx> FromIterator<(Idx, T)> for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator + Default, Idx: Copy + Ord {
+  /// Collects `(index, value)` pairs in any order.
+  ///
+  /// If an index is repeated the last value is kept, matching [SparseVec::set].
+  fn from_iter<I>(iter: I) -> Self
+    where I: IntoIterator<Item = (Idx, T)> {
+    Self::from_iter_with(iter,|_,_,value| value)
+  }
+}
+
+impl<T, Alloc, Idx> Index<Idx> for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  type Output = T;
+
+  #[track_caller]
+  fn index(&self, index: Idx) -> &Self::Output {
+    self.get(index).expect("accessed and empty index")
+  }
+}
+
+impl<T, Alloc, Idx> IndexMut<Idx> for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  #[track_caller]
+  fn index_mut(&mut self, index: Idx) -> &mut Self::Output {
+    self.get_mut(index).expect("accessed and empty index")
+  }
+}
+
+impl<T,Alloc,Idx> Eq for SparseVec<T,Alloc,Idx>
+  where T: Eq, Alloc: Allocator, Idx: Eq {}
+
+impl<T1,Alloc1,T2,Alloc2,Idx> PartialEq<SparseVec<T2,Alloc2,Idx>> for SparseVec<T1,Alloc1,Idx>
+  where T1: PartialEq<T2>, Alloc1: Allocator, Alloc2: Allocator, Idx: PartialEq {
+  fn eq(&self, rhs: &SparseVec<T2,Alloc2,Idx>) -> bool {
+    self.indices == rhs.indices && self.values == rhs.values
+  }
+}
+
+impl<T,Alloc,Idx> Ord for SparseVec<T,Alloc,Idx>
+  where T: Ord, Alloc: Allocator, Idx: Copy + Ord {
+  /// Compares the `(index, value)` pairs lexicographically in index order.
+  fn cmp(&self, rhs: &Self) -> Ordering { self.iter().cmp(rhs.iter()) }
+}
+
+impl<T1,Alloc1,T2,Alloc2,Idx> PartialOrd<SparseVec<T2,Alloc2,Idx>> for SparseVec<T1,Alloc1,Idx>
+  where T1: PartialOrd<T2>, Alloc1: Allocator, Alloc2: Allocator, Idx: Copy + Ord {
+  /// Compares the `(index, value)` pairs lexicographically in index order.
+  fn partial_cmp(&self, rhs: &SparseVec<T2,Alloc2,Idx>) -> Option<Ordering> {
+    let mut lhs = self.iter();
+    let mut rhs = rhs.iter();
+
+    loop {
+      let ((lhs_index,lhs_value),(rhs_index,rhs_value)) = match (lhs.next(),rhs.next()) {
+        (None,None) => return Some(Ordering::Equal),
+        (None,Some(_)) => return Some(Ordering::Less),
+        (Some(_),None) => return Some(Ordering::Greater),
+        (Some(lhs),Some(rhs)) => (lhs,rhs),
+      };
+
+      match lhs_index.cmp(&rhs_index) {
+        Ordering::Equal => (),
+        order => return Some(order),
+      }
+      match lhs_value.partial_cmp(rhs_value)? {
+        Ordering::Equal => (),
+        order => return Some(order),
+      }
+    }
+  }
+}
+
+impl<T, Alloc, Idx> Hash for SparseVec<T, Alloc, Idx>
+  where T: Hash, Alloc: Allocator, Idx: Hash {
+  fn hash<H>(&self, state: &mut H)
+    where H: Hasher {
+    self.indices.hash(state);
+    self.values.hash(state);
+  }
+}
+
+impl<T, Alloc, Idx> Debug for SparseVec<T, Alloc, Idx>
+  where T: Debug, Alloc: Allocator, Idx: Copy + Ord + Debug {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    fmt.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<T, Alloc, Idx> Display for SparseVec<T, Alloc, Idx>
+  where T: Display, Alloc: Allocator, Idx: Copy + Ord + Display {
+  /// Formats the values as `[index]=value` separated by spaces, or `[]` if `self` is empty.
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    if self.is_empty() { return fmt.write_str("[]") }
+
+    for (position,(index,value)) in self.iter().enumerate() {
+      if position != 0 { fmt.write_str(" ")? }
+
+      write!(fmt,"[{index}]={value}")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::alloc::{AllocError,Global,Layout};
+  use alloc::collections::BTreeMap;
+  use alloc::format;
+  use alloc::vec;
+  use core::cell::Cell;
+  use core::ptr::NonNull;
+
+  /// SparseVec of the tests.
+  type TestVec<T> = SparseVec<T, Global>;
+
+  /// Collects the `(index, value)` pairs of `sparse_vec`.
+  fn pairs<T>(sparse_vec: &TestVec<T>) -> Vec<(usize, T)>
+    where T: Clone {
+    sparse_vec.iter().map(|(index,value)| (index,value.clone())).collect()
+  }
+  /// Value which counts its drops.
+  #[derive(Debug)]
+  struct Counted<'a>(usize, &'a Cell<usize>);
+
+  impl Drop for Counted<'_> {
+    fn drop(&mut self) { self.1.set(self.1.get() + 1) }
+  }
+
+  /// Constructs a SparseVec storing a [Counted] at each of `indices`.
+  fn counted<'a>(indices: &[usize], drops: &'a Cell<usize>) -> TestVec<Counted<'a>> {
+    indices.iter().map(|&index| (index,Counted(index,drops))).collect()
+  }
+  /// Collects the set indices of `sparse_vec`.
+  fn indices<T>(sparse_vec: &TestVec<T>) -> Vec<usize> { sparse_vec.indices().collect() }
+  /// Returns the next pseudo-random number of `state`.
+  fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+  }
+  /// Generates pseudo-random `(index, value)` pairs with repeated indices from `seed`.
+  fn random_pairs(seed: u64) -> Vec<(usize, u64)> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    let len = xorshift(&mut state) % 40;
+
+    (0..len).map(|_| ((xorshift(&mut state) % 32) as usize,xorshift(&mut state) % 1000)).collect()
+  }
+
+  /// Most bytes [BogusAlloc] will allocate at once.
+  const BOGUS_LIMIT: usize = 64;
+
+  /// Allocator which fails any allocation larger than [BOGUS_LIMIT].
+  #[derive(Clone,Copy,Default)]
+  struct BogusAlloc;
+
+  unsafe impl Allocator for BogusAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+      if layout.size() > BOGUS_LIMIT { Err(AllocError) } else { Global.allocate(layout) }
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+      unsafe { Global.deallocate(ptr,layout) }
+    }
+  }
+
+  #[test]
+  fn remove_ends_and_middle() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remove(4),Some('b'));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(9,'c')]);
+    assert_eq!(sparse_vec.remove(1),Some('a'));
+    assert_eq!(pairs(&sparse_vec),vec![(9,'c')]);
+    assert_eq!(sparse_vec.remove(9),Some('c'));
+    assert!(sparse_vec.is_empty());
+  }
+  #[test]
+  fn remove_unset() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remove(0),None);
+    assert_eq!(sparse_vec.remove(2),None);
+    assert_eq!(sparse_vec.remove(5),None);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+    assert_eq!(TestVec::<char>::new().remove(0),None);
+  }
+  #[test]
+  fn set_remove_interleaved() {
+    let mut sparse_vec = TestVec::new();
+
+    assert_eq!(sparse_vec.set(5,'a'),None);
+    assert_eq!(sparse_vec.set(2,'b'),None);
+    assert_eq!(sparse_vec.remove(5),Some('a'));
+    assert_eq!(sparse_vec.set(7,'c'),None);
+    assert_eq!(sparse_vec.set(2,'d'),Some('b'));
+    assert_eq!(sparse_vec.count(),2);
+    assert_eq!(pairs(&sparse_vec),vec![(2,'d'),(7,'c')]);
+    assert_eq!(sparse_vec.remove(2),Some('d'));
+    assert_eq!(sparse_vec.remove(2),None);
+    assert_eq!(sparse_vec.set(0,'e'),None);
+    assert_eq!(sparse_vec.count(),2);
+    assert_eq!(pairs(&sparse_vec),vec![(0,'e'),(7,'c')]);
+  }
+  #[test]
+  fn fill_range_unbounded() {
+    extern crate std;
+
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(7,'b')].into_iter().collect();
+
+    for range in [(Bound::Unbounded,Bound::Unbounded),(Bound::Included(5),Bound::Unbounded)] {
+      let filled = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.fill_range(range,'c')));
+
+      assert!(filled.is_err());
+      assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(7,'b')]);
+    }
+    sparse_vec.fill_range(6..=8,'c');
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(6,'c'),(7,'c'),(8,'c')]);
+  }
+  #[test]
+  fn try_from_parts_errors() {
+    let error = TestVec::try_from_parts(vec![1,2],vec!['a']).unwrap_err();
+
+    assert_eq!(error.kind,FromPartsErrorKind::LengthMismatch{indices: 2,values: 1});
+    assert_eq!(error.into_parts(),(vec![1,2],vec!['a']));
+
+    let error = TestVec::try_from_parts(vec![1,4,4],vec!['a','b','c']).unwrap_err();
+
+    assert_eq!(error.kind,FromPartsErrorKind::Duplicate{at: 2});
+    assert_eq!((error.indices,error.values),(vec![1,4,4],vec!['a','b','c']));
+
+    let error = TestVec::try_from((vec![1,4,3],vec!['a','b','c'])).unwrap_err();
+
+    assert_eq!(error.kind,FromPartsErrorKind::NotSorted{at: 2});
+    assert_eq!(error.into_parts(),(vec![1,4,3],vec!['a','b','c']));
+
+    let sparse_vec = TestVec::try_from((vec![1,4],vec!['a','b'])).unwrap();
+
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+  }
+  #[test]
+  fn set_many_unsorted() {
+    extern crate std;
+
+    let mut sparse_vec: TestVec<char> = [(0,'a'),(10,'b')].into_iter().collect();
+    let stored = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.set_many([(5,'x'),(3,'y')])));
+
+    assert!(stored.is_err());
+    assert_eq!(pairs(&sparse_vec),vec![(0,'a'),(10,'b')]);
+    sparse_vec.set_many([(3,'y'),(5,'x'),(10,'c')]);
+    assert_eq!(pairs(&sparse_vec),vec![(0,'a'),(3,'y'),(5,'x'),(10,'c')]);
+  }
+  #[test]
+  fn retain_none_all_alternating() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,3,5,7,9],&drops);
+
+    sparse_vec.retain(|_,_| true);
+    assert_eq!(indices(&sparse_vec),vec![1,3,5,7,9]);
+    assert_eq!(drops.get(),0);
+    sparse_vec.retain(|index,value| {
+      assert_eq!(index,value.0);
+      index % 4 == 1
+    });
+    assert_eq!(indices(&sparse_vec),vec![1,5,9]);
+    assert_eq!(drops.get(),2);
+    sparse_vec.retain(|_,_| false);
+    assert!(sparse_vec.is_empty());
+    assert_eq!(drops.get(),5);
+  }
+  #[test]
+  fn retain_panic() {
+    extern crate std;
+
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,2,3,4,5],&drops);
+    let retained = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.retain(|index,_| {
+      if index == 4 { panic!("retain") }
+
+      index != 2
+    })));
+
+    assert!(retained.is_err());
+    assert_eq!(indices(&sparse_vec),vec![1,3,4,5]);
+    assert!(sparse_vec.iter().all(|(index,value)| index == value.0));
+    assert_eq!(drops.get(),1);
+    drop(sparse_vec);
+    assert_eq!(drops.get(),5);
+  }
+  #[test]
+  fn extract_if_all() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,3,5],&drops);
+    let extracted: Vec<_> = sparse_vec.extract_if(|_,_| true).map(|(index,value)| (index,value.0)).collect();
+
+    assert_eq!(extracted,vec![(1,1),(3,3),(5,5)]);
+    assert!(sparse_vec.is_empty());
+    assert_eq!(drops.get(),3);
+  }
+  #[test]
+  fn extract_if_early_drop() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,2,3,4,5,6],&drops);
+    let mut extract = sparse_vec.extract_if(|index,_| index % 2 == 0);
+
+    assert_eq!(extract.next().map(|(index,_)| index),Some(2));
+    drop(extract);
+    assert_eq!(drops.get(),1);
+    assert_eq!(indices(&sparse_vec),vec![1,3,4,5,6]);
+    assert!(sparse_vec.iter().all(|(index,value)| index == value.0));
+    drop(sparse_vec);
+    assert_eq!(drops.get(),6);
+  }
+  #[test]
+  fn extract_if_panic() {
+    extern crate std;
+
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,2,3,4,5],&drops);
+    let extracted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+      sparse_vec.extract_if(|index,_| {
+        if index == 4 { panic!("extract_if") }
+
+        index == 2
+      }).count()
+    }));
+
+    assert!(extracted.is_err());
+    assert_eq!(drops.get(),1);
+    assert_eq!(indices(&sparse_vec),vec![1,3,4,5]);
+    assert!(sparse_vec.iter().all(|(index,value)| index == value.0));
+    drop(sparse_vec);
+    assert_eq!(drops.get(),5);
+  }
+  #[test]
+  fn get_disjoint_mut_unset() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let [a,unset,c] = sparse_vec.get_disjoint_mut([1,5,9]);
+
+    assert_eq!(unset,None);
+    mem::swap(a.unwrap(),c.unwrap());
+    assert_eq!(pairs(&sparse_vec),vec![(1,'c'),(4,'b'),(9,'a')]);
+    assert_eq!(TestVec::<char>::new().get_disjoint_mut([0,1]),[None,None]);
+  }
+  #[test]
+  fn get_disjoint_mut_aliasing() {
+    let mut sparse_vec: TestVec<usize> = (0..8).map(|index| (index * 2,index)).collect();
+    let values = sparse_vec.get_disjoint_mut([14,0,6,2]);
+
+    // Writes through every reference while all are live.
+    for value in values.into_iter().flatten() { *value += 100 }
+    assert_eq!(sparse_vec.values().copied().collect::<Vec<_>>(),vec![100,101,2,103,4,5,6,107]);
+  }
+  #[test]
+  #[should_panic = "got a repeated index"]
+  fn get_disjoint_mut_repeated() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    sparse_vec.get_disjoint_mut([4,1,4]);
+  }
+  #[test]
+  fn try_reserve_failure() {
+    let mut sparse_vec = SparseVec::<u64, BogusAlloc>::new();
+
+    sparse_vec.set(3,3);
+    sparse_vec.set(1,1);
+    assert!(sparse_vec.try_reserve(BOGUS_LIMIT).is_err());
+    assert_eq!(sparse_vec.as_parts(),(&[1,3][..],&[1,3][..]));
+    assert!(sparse_vec.try_reserve(2).is_ok());
+    assert!(sparse_vec.capacity() >= 4);
+  }
+  #[test]
+  fn try_set_failure() {
+    let mut sparse_vec = SparseVec::<u64, BogusAlloc>::new();
+    let full = BOGUS_LIMIT / mem::size_of::<u64>();
+
+    for index in 0..full { assert_eq!(sparse_vec.try_set(index * 2,index as u64),Ok(None)) }
+    assert_eq!(sparse_vec.capacity(),full);
+    assert!(sparse_vec.try_set(5,99).is_err());
+    assert_eq!(sparse_vec.count(),full);
+    assert_eq!(sparse_vec.get(5),None);
+    assert!(sparse_vec.iter().all(|(index,&value)| index == value as usize * 2));
+    assert_eq!(sparse_vec.try_set(4,99),Ok(Some(2)));
+  }
+  #[test]
+  fn from_iter_model() {
+    for seed in 0..256 {
+      let pairs = random_pairs(seed);
+      let model: BTreeMap<_,_> = pairs.iter().copied().collect();
+      let sparse_vec: TestVec<u64> = pairs.into_iter().collect();
+
+      assert!(sparse_vec.iter().map(|(index,&value)| (index,value)).eq(model));
+    }
+  }
+  #[test]
+  fn from_iter_with_model() {
+    for seed in 0..256 {
+      let pairs = random_pairs(seed);
+      let mut model = BTreeMap::new();
+
+      for &(index,value) in &pairs { *model.entry(index).or_insert(0) += value }
+
+      let sparse_vec = TestVec::from_iter_with(pairs,|_,earlier,later| earlier + later);
+
+      assert!(sparse_vec.iter().map(|(index,&value)| (index,value)).eq(model));
+    }
+  }
+  #[test]
+  fn extend_model() {
+    for seed in 0..256 {
+      let (initial,batch) = (random_pairs(seed),random_pairs(seed + 256));
+      let mut model: BTreeMap<_,_> = initial.iter().copied().collect();
+      let mut sparse_vec: TestVec<u64> = initial.into_iter().collect();
+
+      model.extend(batch.iter().copied());
+      sparse_vec.extend(batch);
+      assert!(sparse_vec.iter().map(|(index,&value)| (index,value)).eq(model));
+    }
+  }
+  #[test]
+  fn count_in_range_model() {
+    for seed in 0..64 {
+      let sparse_vec: TestVec<u64> = random_pairs(seed).into_iter().collect();
+
+      for start in 0..34 {
+        for end in start..34 {
+          assert_eq!(sparse_vec.count_in_range(start..end),sparse_vec.range(start..end).count());
+          assert_eq!(sparse_vec.count_in_range(start..=end),sparse_vec.range(start..=end).count());
+        }
+        assert_eq!(sparse_vec.count_in_range(start..),sparse_vec.range(start..).count());
+        assert_eq!(sparse_vec.count_in_range(..start),sparse_vec.range(..start).count());
+      }
+      assert_eq!(sparse_vec.count_in_range(..),sparse_vec.count());
+    }
+  }
+  #[test]
+  fn into_dense_lengths() {
+    let drops = Cell::new(0);
+    let sparse_vec = counted(&[1,4],&drops);
+    let sparse_vec = sparse_vec.into_dense(2).unwrap_err();
+    let sparse_vec = sparse_vec.into_dense(4).unwrap_err();
+
+    assert_eq!(indices(&sparse_vec),vec![1,4]);
+    assert_eq!(drops.get(),0);
+
+    let dense = sparse_vec.into_dense(5).unwrap();
+
+    assert_eq!(dense.iter().map(|value| value.as_ref().map(|value| value.0)).collect::<Vec<_>>(),vec![None,Some(1),None,None,Some(4)]);
+    drop(dense);
+    assert_eq!(drops.get(),2);
+
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert_eq!(sparse_vec.clone().into_dense(7).unwrap(),vec![None,Some('a'),None,None,Some('b'),None,None]);
+    assert_eq!(TestVec::<char>::new().into_dense(2).unwrap(),vec![None,None]);
+  }
+  #[test]
+  fn get_many_repeated() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let got: Vec<_> = sparse_vec.get_many(&[0,1,1,4,4,4,5,9,9,12]).collect();
+
+    assert_eq!(got,vec![
+      (0,None),(1,Some(&'a')),(1,Some(&'a')),(4,Some(&'b')),(4,Some(&'b')),(4,Some(&'b')),(5,None),
+      (9,Some(&'c')),(9,Some(&'c')),(12,None),
+    ]);
+    assert_eq!(TestVec::<char>::new().get_many(&[2,2]).collect::<Vec<_>>(),vec![(2,None),(2,None)]);
+  }
+  #[test]
+  fn alter_absent() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert!(!sparse_vec.alter(2,|value| {
+      assert_eq!(value,None);
+      None
+    }));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+    assert!(sparse_vec.alter(2,|_| Some('c')));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(2,'c'),(4,'b')]);
+  }
+  #[test]
+  fn alter_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert!(sparse_vec.alter(4,|value| value.map(|value| value.to_ascii_uppercase())));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'B'),(9,'c')]);
+    assert!(!sparse_vec.alter(4,|value| {
+      assert_eq!(value,Some('B'));
+      None
+    }));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(9,'c')]);
+  }
+  #[test]
+  fn alter_panic() {
+    extern crate std;
+
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4,9],&drops);
+    let altered = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.alter(4,|_| panic!("alter"))));
+
+    assert!(altered.is_err());
+    assert_eq!(drops.get(),1);
+    assert_eq!(indices(&sparse_vec),vec![1,9]);
+    assert_eq!(sparse_vec.indices_slice().len(),sparse_vec.values_slice().len());
+    assert!(sparse_vec.iter().all(|(index,value)| index == value.0));
+    drop(sparse_vec);
+    assert_eq!(drops.get(),3);
+  }
+  #[test]
+  fn remap_indices_reverse() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(6,'c'),(9,'d')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remap_indices(|index| 10 - index),Ok(()));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'d'),(4,'c'),(6,'b'),(9,'a')]);
+  }
+  #[test]
+  fn remap_indices_permutation() {
+    let mut sparse_vec: TestVec<usize> = (0..8).map(|index| (index,index)).collect();
+
+    // Rotates every index which makes one cycle through all the values.
+    assert_eq!(sparse_vec.remap_indices(|index| (index + 3) % 8 * 2),Ok(()));
+    assert!(sparse_vec.iter().all(|(index,&value)| index == (value + 3) % 8 * 2));
+    assert_eq!(indices(&sparse_vec),vec![0,2,4,6,8,10,12,14]);
+  }
+  #[test]
+  fn remap_indices_collision() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(5,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remap_indices(|index| index / 2),Err(DuplicateIndexError{index: 2}));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b'),(5,'c')]);
+  }
+  #[test]
+  fn remap_indices_identity() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(5,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remap_indices(|index| index),Ok(()));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b'),(5,'c')]);
+    sparse_vec.remap_indices_monotonic(|index| index);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b'),(5,'c')]);
+    sparse_vec.remap_indices_monotonic(|index| index * 3);
+    assert_eq!(pairs(&sparse_vec),vec![(3,'a'),(12,'b'),(15,'c')]);
+  }
+  #[test]
+  fn remap_indices_monotonic_panic() {
+    extern crate std;
+
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4,9],&drops);
+    let remapped = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+      sparse_vec.remap_indices_monotonic(|index| if index == 9 { 0 } else { index })
+    }));
+
+    assert!(remapped.is_err());
+    assert!(sparse_vec.is_empty());
+    assert_eq!(drops.get(),3);
+  }
+  #[test]
+  fn move_entry_onto_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(6,'c'),(9,'d')].into_iter().collect();
+
+    assert_eq!(sparse_vec.move_entry(1,6),Ok(Some('c')));
+    assert_eq!(pairs(&sparse_vec),vec![(4,'b'),(6,'a'),(9,'d')]);
+    assert_eq!(sparse_vec.move_entry(9,4),Ok(Some('b')));
+    assert_eq!(pairs(&sparse_vec),vec![(4,'d'),(6,'a')]);
+  }
+  #[test]
+  fn move_entry_up() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(6,'c'),(9,'d')].into_iter().collect();
+
+    assert_eq!(sparse_vec.move_entry(1,7),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(4,'b'),(6,'c'),(7,'a'),(9,'d')]);
+    assert_eq!(sparse_vec.move_entry(7,8),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(4,'b'),(6,'c'),(8,'a'),(9,'d')]);
+    assert_eq!(sparse_vec.move_entry(4,12),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(6,'c'),(8,'a'),(9,'d'),(12,'b')]);
+  }
+  #[test]
+  fn move_entry_down() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(6,'c'),(9,'d')].into_iter().collect();
+
+    assert_eq!(sparse_vec.move_entry(9,2),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(2,'d'),(4,'b'),(6,'c')]);
+    assert_eq!(sparse_vec.move_entry(6,5),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(2,'d'),(4,'b'),(5,'c')]);
+    assert_eq!(sparse_vec.move_entry(4,0),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(0,'b'),(1,'a'),(2,'d'),(5,'c')]);
+  }
+  #[test]
+  fn move_entry_same_and_unset() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert_eq!(sparse_vec.move_entry(4,4),Ok(None));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+    assert_eq!(sparse_vec.move_entry(2,4),Err(MoveError{from: 2}));
+    assert_eq!(sparse_vec.move_entry(2,2),Err(MoveError{from: 2}));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+  }
+  #[test]
+  fn search_with_hint_model() {
+    for seed in 0..16 {
+      let sparse_vec: TestVec<u64> = random_pairs(seed).into_iter().collect();
+      let hints = (0..sparse_vec.count() + 3).chain([usize::MAX]);
+
+      for hint in hints {
+        for index in 0..34 {
+          let slot = sparse_vec.slot_of(index);
+
+          assert_eq!(sparse_vec.search_with_hint(index,hint),slot);
+          assert_eq!(sparse_vec.get_with_hint(index,hint),(sparse_vec.get(index),slot.unwrap_or_else(|slot| slot)));
+
+          let (mut hinted,mut model) = (sparse_vec.clone(),sparse_vec.clone());
+
+          assert_eq!(hinted.set_with_hint(index,1000,hint),(model.set(index,1000),slot.unwrap_or_else(|slot| slot)));
+          assert_eq!(hinted,model);
+        }
+      }
+    }
+  }
+  #[test]
+  fn search_with_hint_empty() {
+    let mut sparse_vec = TestVec::new();
+
+    for hint in [0,1,usize::MAX] {
+      assert_eq!(sparse_vec.search_with_hint(5,hint),Err(0));
+      assert_eq!(sparse_vec.get_with_hint(5,hint),(None,0));
+    }
+    assert_eq!(sparse_vec.set_with_hint(5,'a',usize::MAX),(None,0));
+    assert_eq!(pairs(&sparse_vec),vec![(5,'a')]);
+  }
+  #[test]
+  fn get_many_mut_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let [c,a] = sparse_vec.get_many_mut([9,1]).unwrap();
+
+    mem::swap(c,a);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'c'),(4,'b'),(9,'a')]);
+  }
+  #[test]
+  fn get_many_mut_repeated_and_unset() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert!(sparse_vec.get_many_mut([4,1,4]).is_none());
+    assert!(sparse_vec.get_many_mut([1,5]).is_none());
+    assert!(sparse_vec.get_many_mut([5,5]).is_none());
+    assert!(TestVec::<char>::new().get_many_mut([0]).is_none());
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b'),(9,'c')]);
+  }
+  #[test]
+  fn get2_mut_both_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+    let (Some(a),Some(b)) = sparse_vec.get2_mut(4,1) else { panic!("unset index") };
+
+    mem::swap(a,b);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'b'),(4,'a')]);
+  }
+  #[test]
+  fn get2_mut_one_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert_eq!(sparse_vec.get2_mut(1,2),(Some(&mut 'a'),None));
+    assert_eq!(sparse_vec.get2_mut(2,4),(None,Some(&mut 'b')));
+  }
+  #[test]
+  fn get2_mut_neither_set() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert_eq!(sparse_vec.get2_mut(0,2),(None,None));
+    assert_eq!(TestVec::<char>::new().get2_mut(0,1),(None,None));
+  }
+  #[test]
+  #[should_panic = "got a repeated index"]
+  fn get2_mut_same() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    sparse_vec.get2_mut(4,4);
+  }
+  #[test]
+  fn ord_prefix_and_index() {
+    let short: TestVec<u32> = [(0,1)].into_iter().collect();
+    let long: TestVec<u32> = [(0,1),(5,2)].into_iter().collect();
+    let greater: TestVec<u32> = [(0,2)].into_iter().collect();
+    let later: TestVec<u32> = [(1,1)].into_iter().collect();
+
+    assert!(short < long && long < greater);
+    assert!(greater < later);
+    for (lhs,rhs) in [(&short,&long),(&long,&greater),(&greater,&later),(&long,&long)] {
+      assert_eq!(lhs.partial_cmp(rhs),Some(lhs.cmp(rhs)));
+      assert_eq!(rhs.partial_cmp(lhs),Some(rhs.cmp(lhs)));
+    }
+    assert_eq!(TestVec::<u32>::new().cmp(&short),Ordering::Less);
+  }
+  #[test]
+  fn hash_insertion_order() {
+    extern crate std;
+
+    use std::hash::{BuildHasher,RandomState};
+
+    let mut forwards = TestVec::new();
+    let mut backwards = TestVec::new();
+
+    for index in [0,5,9] { forwards.set(index,index * 2); }
+    for index in [9,5,0] { backwards.set(index,index * 2); }
+
+    let state = RandomState::new();
+
+    assert_eq!(state.hash_one(&forwards),state.hash_one(&backwards));
+  }
+  #[test]
+  fn display() {
+    let sparse_vec: TestVec<char> = [(9,'c'),(0,'a'),(5,'b')].into_iter().collect();
+
+    assert_eq!(format!("{sparse_vec}"),"[0]=a [5]=b [9]=c");
+    assert_eq!(format!("{}",TestVec::<char>::new()),"[]");
+  }
+  #[test]
+  fn merge_with_sum() {
+    let inputs = [
+      (vec![(1,1),(3,2)],vec![(5,10),(8,20)],vec![(1,1),(3,2),(5,10),(8,20)]),
+      (vec![(1,1),(5,2),(9,3)],vec![(0,10),(5,20),(7,30)],vec![(0,10),(1,1),(5,22),(7,30),(9,3)]),
+      (vec![(1,1),(5,2)],vec![(1,10),(5,20)],vec![(1,11),(5,22)]),
+    ];
+
+    for (lhs,rhs,merged) in inputs {
+      let mut sparse_vec: TestVec<u32> = lhs.into_iter().collect();
+
+      sparse_vec.merge_with(rhs.into_iter().collect(),|lhs,rhs| *lhs += rhs);
+      assert_eq!(pairs(&sparse_vec),merged);
+    }
+  }
+  #[test]
+  fn append_with_sum() {
+    let inputs = [
+      (vec![(1,1),(3,2)],vec![(5,10),(8,20)],vec![(1,1),(3,2),(5,10),(8,20)]),
+      (vec![(5,10),(8,20)],vec![(1,1),(3,2)],vec![(1,1),(3,2),(5,10),(8,20)]),
+      (vec![(1,1),(5,2),(9,3)],vec![(0,10),(5,20),(7,30)],vec![(0,10),(1,1),(5,22),(7,30),(9,3)]),
+      (vec![(1,1),(5,2)],vec![(1,10),(5,20)],vec![(1,11),(5,22)]),
+    ];
+
+    for (lhs,rhs,appended) in inputs {
+      let mut sparse_vec: TestVec<u32> = lhs.into_iter().collect();
+      let mut other: TestVec<u32> = rhs.into_iter().collect();
+
+      sparse_vec.append_with(&mut other,|_,ours,theirs| ours + theirs);
+      assert_eq!(pairs(&sparse_vec),appended);
+      assert!(other.is_empty());
+    }
+  }
+  #[test]
+  fn append_replaces() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(5,'b')].into_iter().collect();
+    let mut other: TestVec<char> = [(5,'c'),(7,'d')].into_iter().collect();
+
+    sparse_vec.append(&mut other);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(5,'c'),(7,'d')]);
+    assert!(other.is_empty());
+    sparse_vec.append(&mut other);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(5,'c'),(7,'d')]);
+
+    let mut empty = TestVec::new();
+
+    empty.append(&mut sparse_vec);
+    assert_eq!(pairs(&empty),vec![(1,'a'),(5,'c'),(7,'d')]);
+    assert!(sparse_vec.is_empty());
+  }
+  #[test]
+  fn split_off_bounds() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let splits = [
+      (0,vec![],vec![(1,'a'),(4,'b'),(9,'c')]),
+      (4,vec![(1,'a')],vec![(4,'b'),(9,'c')]),
+      (5,vec![(1,'a'),(4,'b')],vec![(9,'c')]),
+      (10,vec![(1,'a'),(4,'b'),(9,'c')],vec![]),
+    ];
+
+    for (bound,low,high) in splits {
+      let mut low_vec = sparse_vec.clone();
+      let high_vec = low_vec.split_off(bound);
+
+      assert_eq!(pairs(&low_vec),low);
+      assert_eq!(pairs(&high_vec),high);
+    }
+    assert!(TestVec::<char>::new().split_off(0).is_empty());
+  }
+  #[test]
+  fn clear_keeps_capacity() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4,9],&drops);
+    let capacity = sparse_vec.capacity();
+
+    sparse_vec.clear();
+    assert!(sparse_vec.is_empty());
+    assert_eq!(sparse_vec.capacity(),capacity);
+    assert_eq!(drops.get(),3);
+  }
+  #[test]
+  fn take_and_replace() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4],&drops);
+    let taken = sparse_vec.take();
+
+    assert!(sparse_vec.is_empty());
+    assert_eq!(indices(&taken),vec![1,4]);
+    assert_eq!(drops.get(),0);
+
+    let replaced = sparse_vec.replace(counted(&[7],&drops));
+
+    assert!(replaced.is_empty());
+    assert_eq!(indices(&sparse_vec),vec![7]);
+
+    let replaced = sparse_vec.replace(taken);
+
+    assert_eq!(indices(&replaced),vec![7]);
+    assert_eq!(indices(&sparse_vec),vec![1,4]);
+    drop(replaced);
+    assert_eq!(drops.get(),1);
+    drop(sparse_vec);
+    assert_eq!(drops.get(),3);
+  }
+  #[test]
+  fn adjacent_pairs_short() {
+    let mut calls = 0;
+
+    for mut sparse_vec in [TestVec::new(),[(4,'a')].into_iter().collect()] {
+      assert_eq!(sparse_vec.iter_adjacent_pairs().len(),0);
+      sparse_vec.for_each_adjacent_pair_mut(|_,_,_,_| calls += 1);
+    }
+    assert_eq!(calls,0);
+  }
+  #[test]
+  fn adjacent_pairs() {
+    let mut sparse_vec: TestVec<u32> = [(1,1),(4,2),(9,3),(10,4)].into_iter().collect();
+    let adjacent: Vec<_> = sparse_vec.iter_adjacent_pairs().map(|((lower,_),(upper,_))| (lower,upper)).collect();
+
+    assert_eq!(adjacent,vec![(1,4),(4,9),(9,10)]);
+
+    let mut visited = Vec::new();
+
+    // Each call sees the value written by the previous call.
+    sparse_vec.for_each_adjacent_pair_mut(|lower,lower_value,upper,upper_value| {
+      visited.push((lower,upper));
+      *upper_value += *lower_value;
+      *lower_value = 0;
+    });
+    assert_eq!(visited,vec![(1,4),(4,9),(9,10)]);
+    assert_eq!(pairs(&sparse_vec),vec![(1,0),(4,0),(9,0),(10,10)]);
+  }
+  #[test]
+  fn rank_select() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.rank(0),0);
+    assert_eq!(sparse_vec.rank(1),0);
+    assert_eq!(sparse_vec.rank(4),1);
+    assert_eq!(sparse_vec.rank(5),2);
+    assert_eq!(sparse_vec.rank(9),2);
+    assert_eq!(sparse_vec.rank(10),3);
+    for (index,value) in [(1,'a'),(4,'b'),(9,'c')] {
+      assert_eq!(sparse_vec.select(sparse_vec.rank(index)),Some((index,&value)));
+    }
+    assert_eq!(sparse_vec.select(3),None);
+  }
+  #[test]
+  fn lower_upper_bound() {
+    let sparse_vec: TestVec<char> = [(2,'a'),(3,'b'),(9,'c')].into_iter().collect();
+    // (probe, lower bound, upper bound)
+    let bounds = [(0,0,0),(2,0,1),(3,1,2),(5,2,2),(9,2,3),(10,3,3)];
+
+    for (probe,lower,upper) in bounds {
+      assert_eq!(sparse_vec.lower_bound(probe),lower);
+      assert_eq!(sparse_vec.upper_bound(probe),upper);
+    }
+    assert_eq!(TestVec::<char>::new().lower_bound(5),0);
+    assert_eq!(TestVec::<char>::new().upper_bound(5),0);
+  }
+  #[test]
+  fn range_forms() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let range = |range: (Bound<usize>, Bound<usize>)| sparse_vec.range(range).map(|(index,_)| index).collect::<Vec<_>>();
+
+    assert_eq!(range((Bound::Included(1),Bound::Included(4))),vec![1,4]);
+    assert_eq!(range((Bound::Included(1),Bound::Excluded(4))),vec![1]);
+    assert_eq!(range((Bound::Excluded(1),Bound::Unbounded)),vec![4,9]);
+    assert_eq!(range((Bound::Unbounded,Bound::Included(8))),vec![1,4]);
+    assert_eq!(range((Bound::Unbounded,Bound::Unbounded)),vec![1,4,9]);
+    assert!(range((Bound::Included(9),Bound::Included(1))).is_empty());
+    assert!(range((Bound::Excluded(4),Bound::Excluded(4))).is_empty());
+    assert!(range((Bound::Included(5),Bound::Excluded(2))).is_empty());
+    assert_eq!(sparse_vec.range(..=4).rev().map(|(index,_)| index).collect::<Vec<_>>(),vec![4,1]);
+    assert_eq!(sparse_vec.range((Bound::Included(9),Bound::Included(1))).len(),0);
+    assert_eq!(sparse_vec.range_mut((Bound::Included(9),Bound::Included(1))).len(),0);
+    for (_,value) in sparse_vec.range_mut(4..) { *value = value.to_ascii_uppercase() }
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'B'),(9,'C')]);
+  }
+  #[test]
+  fn iter_unset_gaps() {
+    let sparse_vec: TestVec<char> = [(2,'a'),(3,'b'),(4,'c'),(8,'d')].into_iter().collect();
+    let unset = |range: (Bound<usize>, Bound<usize>)| sparse_vec.iter_unset(range).collect::<Vec<_>>();
+
+    assert_eq!(unset((Bound::Included(0),Bound::Excluded(10))),vec![0,1,5,6,7,9]);
+    assert!(unset((Bound::Included(2),Bound::Included(4))).is_empty());
+    assert_eq!(unset((Bound::Included(1),Bound::Excluded(6))),vec![1,5]);
+    assert_eq!(unset((Bound::Included(6),Bound::Included(9))),vec![6,7,9]);
+    assert_eq!(unset((Bound::Excluded(5),Bound::Excluded(7))),vec![6]);
+    assert!(unset((Bound::Included(7),Bound::Excluded(5))).is_empty());
+    assert_eq!(sparse_vec.iter_unset(usize::MAX - 1..).collect::<Vec<_>>(),vec![usize::MAX - 1,usize::MAX]);
+    assert_eq!(sparse_vec.iter_unset(..).take(3).collect::<Vec<_>>(),vec![0,1,5]);
+  }
+  #[test]
+  fn iter_unset_empty() {
+    let sparse_vec = TestVec::<char>::new();
+
+    assert_eq!(sparse_vec.iter_unset(3..7).collect::<Vec<_>>(),vec![3,4,5,6]);
+    assert_eq!(sparse_vec.iter_unset(..=2).collect::<Vec<_>>(),vec![0,1,2]);
+  }
+  #[test]
+  fn nearest_tie_break() {
+    let sparse_vec: TestVec<char> = [(2,'a'),(6,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.nearest_entry(4),Some((2,&'a')));
+    assert_eq!(sparse_vec.nearest_entry(5),Some((6,&'b')));
+    assert_eq!(sparse_vec.nearest_entry(6),Some((6,&'b')));
+    assert_eq!(sparse_vec.nearest_entry(0),Some((2,&'a')));
+    assert_eq!(sparse_vec.nearest_entry(100),Some((9,&'c')));
+    assert_eq!(sparse_vec.nearest_set_index(8),Some(9));
+    assert_eq!(TestVec::<char>::new().nearest_entry(4),None);
+  }
+  #[test]
+  fn nearest_within() {
+    let sparse_vec: TestVec<char> = [(2,'a'),(6,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.nearest_entry_within(4,2),Some((2,&'a')));
+    assert_eq!(sparse_vec.nearest_entry_within(4,1),None);
+    assert_eq!(sparse_vec.nearest_entry_within(6,0),Some((6,&'b')));
+    assert_eq!(sparse_vec.nearest_entry_within(13,3),None);
+    assert_eq!(sparse_vec.nearest_set_index_within(13,4),Some(9));
+    assert_eq!(TestVec::<char>::new().nearest_set_index_within(4,usize::MAX),None);
+  }
+  #[test]
+  fn small_index() {
+    let mut sparse_vec = SparseVec::<char, Global, u16>::new();
+
+    assert_eq!(sparse_vec.set(u16::MAX,'a'),None);
+    assert_eq!(sparse_vec.set(4,'b'),None);
+    assert_eq!(sparse_vec.set(9,'c'),None);
+    assert_eq!(sparse_vec.get(4),Some(&'b'));
+    assert_eq!(sparse_vec.get(5),None);
+    *sparse_vec.entry(9).or_insert('x') = 'd';
+    sparse_vec.entry(1).or_insert('e');
+    assert_eq!(sparse_vec.remove(4),Some('b'));
+    assert_eq!(sparse_vec.remove(4),None);
+    assert_eq!(sparse_vec.range(2..).collect::<Vec<_>>(),vec![(9,&'d'),(u16::MAX,&'a')]);
+    assert_eq!(sparse_vec.indices_slice(),&[1,9,u16::MAX]);
+  }
+  #[test]
+  fn fill_range_placement() {
+    let sparse_vec: TestVec<char> = [(3,'a'),(5,'b'),(6,'c'),(9,'d')].into_iter().collect();
+    let fills = [
+      ((Bound::Included(4),Bound::Excluded(4)),vec![(3,'a'),(5,'b'),(6,'c'),(9,'d')]),
+      ((Bound::Included(0),Bound::Excluded(2)),vec![(0,'x'),(1,'x'),(3,'a'),(5,'b'),(6,'c'),(9,'d')]),
+      ((Bound::Excluded(9),Bound::Included(11)),vec![(3,'a'),(5,'b'),(6,'c'),(9,'d'),(10,'x'),(11,'x')]),
+      ((Bound::Included(4),Bound::Included(9)),vec![(3,'a'),(4,'x'),(5,'x'),(6,'x'),(7,'x'),(8,'x'),(9,'x')]),
+      ((Bound::Included(2),Bound::Excluded(6)),vec![(2,'x'),(3,'x'),(4,'x'),(5,'x'),(6,'c'),(9,'d')]),
+    ];
+
+    for (range,filled) in fills {
+      let mut sparse_vec = sparse_vec.clone();
+
+      sparse_vec.fill_range(range,'x');
+      assert_eq!(pairs(&sparse_vec),filled);
+    }
+
+    let mut empty = TestVec::new();
+
+    empty.fill_range(2..4,'x');
+    assert_eq!(pairs(&empty),vec![(2,'x'),(3,'x')]);
+  }
+  #[test]
+  fn fill_range_clone_panic() {
+    extern crate std;
+
+    /// Value which panics on its third clone.
+    struct Fuse<'a>(&'a Cell<usize>);
+
+    impl Clone for Fuse<'_> {
+      fn clone(&self) -> Self {
+        self.0.set(self.0.get() + 1);
+        if self.0.get() == 3 { panic!("clone") }
+
+        Self(self.0)
+      }
+    }
+
+    let clones = Cell::new(0);
+    let mut sparse_vec: TestVec<Fuse> = [(0,Fuse(&clones)),(20,Fuse(&clones))].into_iter().collect();
+    let filled = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.fill_range(5..10,Fuse(&clones))));
+
+    assert!(filled.is_err());
+    assert!(sparse_vec.is_empty());
+    assert_eq!(sparse_vec.values_slice().len(),0);
+  }
+  #[test]
+  fn set_slice_blocks() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    sparse_vec.set_slice(3,&['x','y','z']);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(3,'x'),(4,'y'),(5,'z'),(9,'c')]);
+    sparse_vec.set_slice(usize::MAX,&[]);
+    sparse_vec.set_slice(9,&[]);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(3,'x'),(4,'y'),(5,'z'),(9,'c')]);
+    sparse_vec.set_slice(usize::MAX - 1,&['p','q']);
+    assert_eq!(sparse_vec.last(),Some((usize::MAX,&'q')));
+    assert_eq!(sparse_vec.count(),7);
+  }
+  #[test]
+  #[should_panic = "block overflowed the index range"]
+  fn set_slice_overflow() {
+    let mut sparse_vec: TestVec<char> = [(1,'a')].into_iter().collect();
+
+    sparse_vec.set_slice(usize::MAX - 1,&['p','q','r']);
+  }
+  #[test]
+  fn unchecked_matches_get() {
+    for seed in 0..64 {
+      let mut sparse_vec: TestVec<u64> = random_pairs(seed).into_iter().collect();
+
+      for index in 0..32 {
+        if !sparse_vec.is_set(index) { continue }
+
+        let value = *sparse_vec.get(index).unwrap();
+
+        assert_eq!(unsafe { *sparse_vec.get_unchecked(index) },value);
+        unsafe { *sparse_vec.get_unchecked_mut(index) += 1 }
+        assert_eq!(sparse_vec.get(index),Some(&(value + 1)));
+      }
+      for slot in 0..sparse_vec.count() {
+        assert_eq!(unsafe { sparse_vec.value_at_slot_unchecked(slot) },sparse_vec.get_at_slot(slot).unwrap());
+        unsafe { *sparse_vec.value_at_slot_unchecked_mut(slot) = slot as u64 }
+        assert_eq!(sparse_vec.nth(slot).map(|(_,&value)| value),Some(slot as u64));
+      }
+    }
+  }
+  #[test]
+  fn from_sorted_iter_errors() {
+    let sparse_vec = TestVec::from_sorted_iter([(1,'a'),(4,'b'),(9,'c')],Global).unwrap();
+
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b'),(9,'c')]);
+    assert_eq!(TestVec::from_sorted_iter([(1,'a'),(4,'b'),(3,'c')],Global).unwrap_err(),SortedInputError{position: 2,index: 3});
+    assert_eq!(TestVec::from_sorted_iter([(1,'a'),(1,'b')],Global).unwrap_err(),SortedInputError{position: 1,index: 1});
+    assert!(TestVec::<char>::from_sorted_iter([],Global).unwrap().is_empty());
+  }
+  #[test]
+  fn dense_options_round_trip() {
+    let dense = vec![None,Some('a'),None,None,Some('b'),None];
+    let sparse_vec = TestVec::from_dense_options(&dense);
+
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'b')]);
+    assert_eq!(sparse_vec.to_dense(dense.len()),dense);
+    assert_eq!(TestVec::from(dense.clone()),sparse_vec);
+    assert_eq!(TestVec::from(sparse_vec.to_dense(dense.len())),sparse_vec);
+    assert!(TestVec::<char>::from(Vec::new()).is_empty());
+  }
+  #[test]
+  fn to_dense_gaps_and_out_of_range() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.to_dense(6),vec![None,Some('a'),None,None,Some('b'),None]);
+    assert_eq!(sparse_vec.to_dense(4),vec![None,Some('a'),None,None]);
+    assert_eq!(sparse_vec.to_dense(0),vec![]);
+    assert_eq!(sparse_vec.to_dense(10).into_iter().flatten().collect::<Vec<_>>(),vec!['a','b','c']);
+  }
+  #[test]
+  fn from_dense_gaps() {
+    let sparse_vec = TestVec::from_dense([Some(1),None,None,Some(4)]);
+
+    assert_eq!(pairs(&sparse_vec),vec![(0,1),(3,4)]);
+    assert!(TestVec::<u32>::from_dense([None,None]).is_empty());
+  }
+  #[test]
+  fn values_in_index_order() {
+    let mut sparse_vec: TestVec<u32> = [(9,3),(1,1),(4,2)].into_iter().collect();
+
+    assert_eq!(sparse_vec.values().copied().collect::<Vec<_>>(),vec![1,2,3]);
+    for value in sparse_vec.values_mut() { *value *= 10 }
+    assert_eq!(pairs(&sparse_vec),vec![(1,10),(4,20),(9,30)]);
+  }
+  #[test]
+  fn indices_match_iter() {
+    let sparse_vec: TestVec<u32> = [(9,3),(1,1),(4,2)].into_iter().collect();
+
+    assert!(sparse_vec.indices().eq(sparse_vec.iter().map(|(index,_)| index)));
+    assert_eq!(indices(&sparse_vec),vec![1,4,9]);
+    assert_eq!(TestVec::<u32>::new().indices().count(),0);
+  }
+  #[test]
+  fn slices_match_count() {
+    let sparse_vec: TestVec<u32> = [(9,3),(1,1),(4,2)].into_iter().collect();
+
+    assert_eq!(sparse_vec.indices_slice().len(),sparse_vec.count());
+    assert_eq!(sparse_vec.values_slice().len(),sparse_vec.count());
+    assert_eq!(sparse_vec.as_parts(),(&[1,4,9][..],&[1,2,3][..]));
+    assert!(TestVec::<u32>::new().indices_slice().is_empty());
+  }
+  #[test]
+  fn nth_matches_get() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    for n in 0..sparse_vec.count() {
+      let (index,value) = sparse_vec.nth(n).unwrap();
+
+      assert_eq!(sparse_vec.get(index),Some(value));
+    }
+    assert_eq!(sparse_vec.nth(1),Some((4,&'b')));
+    assert_eq!(sparse_vec.nth(3),None);
+  }
+  #[test]
+  fn contains_value() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+
+    assert!(sparse_vec.contains_value(&'a'));
+    assert!(sparse_vec.contains_value(&'b'));
+    assert!(!sparse_vec.contains_value(&'c'));
+    assert!(!TestVec::<char>::new().contains_value(&'a'));
+  }
+  #[test]
+  fn get_or_insert_with() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b')].into_iter().collect();
+    let mut calls = 0;
+
+    assert_eq!(*sparse_vec.get_or_insert_with(4,|| { calls += 1; 'x' }),'b');
+    assert_eq!(calls,0);
+    *sparse_vec.get_or_insert_with(2,|| { calls += 1; 'x' }) = 'y';
+    assert_eq!(calls,1);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(2,'y'),(4,'b')]);
+    assert_eq!(*sparse_vec.get_or_insert_default(7),'\0');
+    assert_eq!(sparse_vec.count(),4);
+  }
+  #[test]
+  fn ends_empty() {
+    let mut sparse_vec = TestVec::<char>::new();
+
+    assert_eq!(sparse_vec.first_index(),None);
+    assert_eq!(sparse_vec.last_index(),None);
+    assert_eq!(sparse_vec.first(),None);
+    assert_eq!(sparse_vec.last(),None);
+    assert_eq!(sparse_vec.first_mut(),None);
+    assert_eq!(sparse_vec.last_mut(),None);
+    sparse_vec.set(4,'a');
+    assert_eq!((sparse_vec.first_index(),sparse_vec.last_index()),(Some(4),Some(4)));
+  }
+  #[test]
+  fn map_values_to_string() {
+    let sparse_vec: TestVec<i32> = [(1,-1),(4,20),(9,300)].into_iter().collect();
+    let mapped = sparse_vec.map_values(|value| format!("{value}"));
+
+    assert_eq!(pairs(&mapped),vec![(1,"-1".into()),(4,"20".into()),(9,"300".into())]);
+    assert_eq!(mapped.indices_slice(),sparse_vec.indices_slice());
+  }
+  #[test]
+  fn map_values_mut_by_index() {
+    let mut sparse_vec: TestVec<usize> = [(1,1),(4,2),(9,3)].into_iter().collect();
+
+    sparse_vec.map_values_mut(|index,value| *value *= index * 2);
+    assert_eq!(pairs(&sparse_vec),vec![(1,2),(4,16),(9,54)]);
+  }
+  #[test]
+  fn index_bounds() {
+    let mut sparse_vec = TestVec::new();
+
+    assert_eq!((sparse_vec.first_index(),sparse_vec.last_index()),(None,None));
+    sparse_vec.extend([(9,'c'),(1,'a'),(4,'b')]);
+    assert_eq!((sparse_vec.first_index(),sparse_vec.last_index()),(Some(1),Some(9)));
+    assert_eq!(sparse_vec.to_dense(sparse_vec.last_index().unwrap() + 1).len(),10);
+  }
+  #[test]
+  fn memory_usage_reserve() {
+    let mut sparse_vec = TestVec::<u32>::new();
+
+    assert_eq!(sparse_vec.memory_usage(),0);
+    sparse_vec.reserve(10);
+
+    let usage = sparse_vec.memory_usage();
+
+    assert!(usage >= 10 * (mem::size_of::<usize>() + mem::size_of::<u32>()));
+    sparse_vec.reserve(sparse_vec.capacity() + 1);
+    assert!(sparse_vec.memory_usage() > usage);
+  }
+  #[test]
+  fn union_supports() {
+    let lhs: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let interleaved: TestVec<u32> = [(0,10),(4,20),(7,30)].into_iter().collect();
+    let disjoint: TestVec<u32> = [(10,10),(11,20)].into_iter().collect();
+    let identical: TestVec<u32> = [(1,10),(4,20),(9,30)].into_iter().collect();
+
+    assert_eq!(lhs.union(&interleaved).collect::<Vec<_>>(),vec![
+      (0,None,Some(&10)),(1,Some(&'a'),None),(4,Some(&'b'),Some(&20)),(7,None,Some(&30)),(9,Some(&'c'),None),
+    ]);
+    assert_eq!(lhs.union(&disjoint).collect::<Vec<_>>(),vec![
+      (1,Some(&'a'),None),(4,Some(&'b'),None),(9,Some(&'c'),None),(10,None,Some(&10)),(11,None,Some(&20)),
+    ]);
+    assert_eq!(lhs.union(&identical).collect::<Vec<_>>(),vec![
+      (1,Some(&'a'),Some(&10)),(4,Some(&'b'),Some(&20)),(9,Some(&'c'),Some(&30)),
+    ]);
+  }
+  #[test]
+  fn intersection_overlap() {
+    let lhs: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let disjoint: TestVec<u32> = [(0,10),(5,20),(10,30)].into_iter().collect();
+    let identical: TestVec<u32> = [(1,10),(4,20),(9,30)].into_iter().collect();
+
+    assert_eq!(lhs.intersection(&disjoint).count(),0);
+    assert_eq!(lhs.intersection(&identical).collect::<Vec<_>>(),vec![(1,&'a',&10),(4,&'b',&20),(9,&'c',&30)]);
+    assert_eq!(lhs.intersection(&TestVec::<u32>::new()).count(),0);
+  }
+  #[test]
+  fn difference_empty_and_superset() {
+    let lhs: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let superset: TestVec<u32> = [(0,0),(1,10),(4,20),(9,30)].into_iter().collect();
+    let partial: TestVec<u32> = [(4,20),(5,30)].into_iter().collect();
+
+    assert_eq!(lhs.difference(&TestVec::<u32>::new()).collect::<Vec<_>>(),vec![(1,&'a'),(4,&'b'),(9,&'c')]);
+    assert_eq!(lhs.difference(&superset).count(),0);
+    assert_eq!(lhs.difference(&partial).collect::<Vec<_>>(),vec![(1,&'a'),(9,&'c')]);
+  }
+  #[test]
+  fn clone_from_equals_source() {
+    let source: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let mut larger: TestVec<char> = (0..20).map(|index| (index,'x')).collect();
+    let mut smaller = TestVec::new();
+
+    larger.clone_from(&source);
+    smaller.clone_from(&source);
+    assert_eq!(larger,source);
+    assert_eq!(smaller,source);
+    assert!(larger.capacity() >= 20);
+  }
+  #[test]
+  fn shrink_capacity() {
+    let mut sparse_vec = TestVec::<u32>::with_capacity(32);
+
+    sparse_vec.extend([(1,1),(4,2),(9,3)]);
+    sparse_vec.shrink_to(8);
+    assert!(sparse_vec.capacity() >= 8 && sparse_vec.capacity() < 32);
+    sparse_vec.shrink_to_fit();
+    assert_eq!(sparse_vec.capacity(),3);
+    assert_eq!(pairs(&sparse_vec),vec![(1,1),(4,2),(9,3)]);
+  }
+  #[test]
+  fn pop_ends() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.pop_first(),Some((1,'a')));
+    assert_eq!(sparse_vec.pop_last(),Some((9,'c')));
+    assert_eq!(pairs(&sparse_vec),vec![(4,'b')]);
+    assert_eq!(sparse_vec.pop_last(),Some((4,'b')));
+    assert_eq!(sparse_vec.pop_first(),None);
+    assert_eq!(sparse_vec.pop_last(),None);
+  }
+  #[test]
+  fn drain_partial() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4,9,12],&drops);
+    let capacity = sparse_vec.capacity();
+    let mut drain = sparse_vec.drain();
+
+    assert_eq!(drain.next().map(|(index,value)| (index,value.0)),Some((1,1)));
+    assert_eq!(drain.next_back().map(|(index,value)| (index,value.0)),Some((12,12)));
+    assert_eq!(drops.get(),2);
+    drop(drain);
+    assert_eq!(drops.get(),4);
+    assert!(sparse_vec.is_empty());
+    assert_eq!(sparse_vec.capacity(),capacity);
+  }
+  #[test]
+  fn drain_range_forms() {
+    let sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+    let drains = [
+      ((Bound::Unbounded,Bound::Unbounded),vec![(1,'a'),(4,'b'),(9,'c')],vec![]),
+      ((Bound::Included(4),Bound::Unbounded),vec![(4,'b'),(9,'c')],vec![(1,'a')]),
+      ((Bound::Unbounded,Bound::Included(4)),vec![(1,'a'),(4,'b')],vec![(9,'c')]),
+      ((Bound::Included(5),Bound::Excluded(9)),vec![],vec![(1,'a'),(4,'b'),(9,'c')]),
+      ((Bound::Included(10),Bound::Unbounded),vec![],vec![(1,'a'),(4,'b'),(9,'c')]),
+    ];
+
+    for (range,drained,kept) in drains {
+      let mut sparse_vec = sparse_vec.clone();
+
+      assert_eq!(sparse_vec.drain_range(range).collect::<Vec<_>>(),drained);
+      assert_eq!(pairs(&sparse_vec),kept);
+    }
+  }
+  #[test]
+  fn remove_range_drops() {
+    let drops = Cell::new(0);
+    let mut sparse_vec = counted(&[1,4,6,9,12],&drops);
+
+    assert_eq!(sparse_vec.remove_range(4..=6),2);
+    assert_eq!(drops.get(),2);
+    assert_eq!(indices(&sparse_vec),vec![1,9,12]);
+    assert_eq!(sparse_vec.remove_range(2..9),0);
+    assert_eq!(drops.get(),2);
+
+    let removed = sparse_vec.remove_range_collect(9..);
+
+    assert_eq!(drops.get(),2);
+    assert_eq!(indices(&removed),vec![9,12]);
+    assert!(removed.iter().all(|(index,value)| index == value.0));
+    assert_eq!(indices(&sparse_vec),vec![1]);
+    drop(removed);
+    assert_eq!(drops.get(),4);
+  }
+  #[test]
+  fn insert_shift() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    sparse_vec.insert_shift(4,Some('x'));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(4,'x'),(5,'b'),(10,'c')]);
+    sparse_vec.insert_shift(0,None);
+    assert_eq!(pairs(&sparse_vec),vec![(2,'a'),(5,'x'),(6,'b'),(11,'c')]);
+    sparse_vec.insert_shift_n(7,3);
+    assert_eq!(pairs(&sparse_vec),vec![(2,'a'),(5,'x'),(6,'b'),(14,'c')]);
+    sparse_vec.insert_shift(20,Some('y'));
+    assert_eq!(pairs(&sparse_vec),vec![(2,'a'),(5,'x'),(6,'b'),(14,'c'),(20,'y')]);
+  }
+  #[test]
+  #[should_panic = "shifted an index past `usize::MAX`"]
+  fn insert_shift_overflow() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(usize::MAX - 1,'b')].into_iter().collect();
+
+    sparse_vec.insert_shift_n(2,2);
+  }
+  #[test]
+  fn insert_shift_at_max() {
+    extern crate std;
+
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(usize::MAX,'b')].into_iter().collect();
+    let shifted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sparse_vec.insert_shift(0,Some('x'))));
+
+    assert!(shifted.is_err());
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(usize::MAX,'b')]);
+  }
+  #[test]
+  fn remove_shift() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remove_shift(4),Some('b'));
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(8,'c')]);
+    assert_eq!(sparse_vec.remove_shift(0),None);
+    assert_eq!(pairs(&sparse_vec),vec![(0,'a'),(7,'c')]);
+    assert_eq!(sparse_vec.remove_shift(20),None);
+    assert_eq!(pairs(&sparse_vec),vec![(0,'a'),(7,'c')]);
+  }
+  #[test]
+  fn remove_shift_range() {
+    let mut sparse_vec: TestVec<char> = [(1,'a'),(4,'b'),(6,'c'),(9,'d'),(usize::MAX,'e')].into_iter().collect();
+
+    assert_eq!(sparse_vec.remove_shift_range(3..7),2);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(5,'d'),(usize::MAX - 4,'e')]);
+    assert_eq!(sparse_vec.remove_shift_range((Bound::Excluded(3),Bound::Excluded(3))),0);
+    assert_eq!(sparse_vec.remove_shift_range(2..=3),0);
+    assert_eq!(pairs(&sparse_vec),vec![(1,'a'),(3,'d'),(usize::MAX - 6,'e')]);
+    assert_eq!(sparse_vec.remove_shift_range(usize::MAX - 6..),1);
+    assert_eq!(sparse_vec.remove_shift_range(..),2);
+    assert!(sparse_vec.is_empty());
+  }
+  #[test]
+  fn offset_indices() {
+    let mut sparse_vec: TestVec<char> = [(2,'a'),(4,'b'),(9,'c')].into_iter().collect();
+
+    assert_eq!(sparse_vec.offset_indices(3),Ok(()));
+    assert_eq!(pairs(&sparse_vec),vec![(5,'a'),(7,'b'),(12,'c')]);
+    assert_eq!(sparse_vec.offset_indices(-5),Ok(()));
+    assert_eq!(pairs(&sparse_vec),vec![(0,'a'),(2,'b'),(7,'c')]);
+    assert_eq!(sparse_vec.offset_indices(-1),Err(OffsetError::Underflow));
+    assert_eq!(sparse_vec.offset_indices(isize::MAX),Ok(()));
+    assert_eq!(sparse_vec.offset_indices(isize::MAX),Err(OffsetError::Overflow));
+    assert_eq!(sparse_vec.offset_indices(isize::MAX - 6),Ok(()));
+    assert_eq!(sparse_vec.last_index(),Some(usize::MAX));
+    assert_eq!(sparse_vec.offset_indices(1),Err(OffsetError::Overflow));
+    assert_eq!(indices(&sparse_vec),vec![usize::MAX - 7,usize::MAX - 5,usize::MAX]);
+    assert_eq!(sparse_vec.offset_indices(isize::MIN),Ok(()));
+    assert_eq!(pairs(&sparse_vec),vec![(isize::MAX as usize - 7,'a'),(isize::MAX as usize - 5,'b'),(isize::MAX as usize,'c')]);
+    assert_eq!(TestVec::<char>::new().offset_indices(isize::MIN),Ok(()));
   }
 }
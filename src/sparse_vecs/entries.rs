@@ -0,0 +1,199 @@
+//! Defines the [Entry] API of [SparseVec].
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+
+use super::SparseVec;
+use alloc::alloc::Allocator;
+use core::mem;
+
+/// A view into a single index of a [SparseVec].
+///
+/// Constructed by [SparseVec::entry].
+pub enum Entry<'a, T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// `index` holds a value.
+  Occupied(OccupiedEntry<'a, T, Alloc, Idx>),
+  /// `index` is unset.
+  Vacant(VacantEntry<'a, T, Alloc, Idx>),
+}
+
+impl<'a, T, Alloc, Idx> Entry<'a, T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy {
+  /// Returns the external index of the entry.
+  pub fn index(&self) -> Idx {
+    match self {
+      Self::Occupied(entry) => entry.index(),
+      Self::Vacant(entry) => entry.index(),
+    }
+  }
+  /// Stores `value` if the entry is vacant and returns the stored value.
+  ///
+  /// # Params
+  ///
+  /// value --- Value to store if the entry is vacant.  
+  pub fn or_insert(self, value: T) -> &'a mut T {
+    match self {
+      Self::Occupied(entry) => entry.into_mut(),
+      Self::Vacant(entry) => entry.insert(value),
+    }
+  }
+  /// Stores the result of `value` if the entry is vacant and returns the stored value.
+  ///
+  /// # Params
+  ///
+  /// value --- Constructs the value to store if the entry is vacant.  
+  pub fn or_insert_with<F>(self, value: F) -> &'a mut T
+    where F: FnOnce() -> T {
+    match self {
+      Self::Occupied(entry) => entry.into_mut(),
+      Self::Vacant(entry) => entry.insert(value()),
+    }
+  }
+  /// Stores the default value if the entry is vacant and returns the stored value.
+  pub fn or_default(self) -> &'a mut T
+    where T: Default {
+    self.or_insert_with(T::default)
+  }
+  /// Calls `f` on the stored value if the entry is occupied.
+  ///
+  /// # Params
+  ///
+  /// f --- Modifies the stored value.  
+  pub fn and_modify<F>(mut self, f: F) -> Self
+    where F: FnOnce(&mut T) {
+    if let Self::Occupied(entry) = &mut self { f(entry.get_mut()) }
+
+    self
+  }
+}
+
+/// A view into an index of a [SparseVec] which holds a value.
+pub struct OccupiedEntry<'a, T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// SparseVec holding the value.
+  pub(super) sparse_vec: &'a mut SparseVec<T, Alloc, Idx>,
+  /// Position of the value in `sparse_vec.values`.
+  ///
+  /// # Invariants
+  ///
+  /// * Less than `sparse_vec.count()`.
+  pub(super) value_index: usize,
+}
+
+impl<'a, T, Alloc, Idx> OccupiedEntry<'a, T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy {
+  /// Returns the external index of the entry.
+  pub fn index(&self) -> Idx {
+    unsafe { *self.sparse_vec.indices.get_unchecked(self.value_index) }
+  }
+  /// Gets the stored value.
+  pub fn get(&self) -> &T {
+    unsafe { self.sparse_vec.values.get_unchecked(self.value_index) }
+  }
+  /// Gets the stored value.
+  pub fn get_mut(&mut self) -> &mut T {
+    unsafe { self.sparse_vec.values.get_unchecked_mut(self.value_index) }
+  }
+  /// Converts the entry into a reference to the stored value.
+  pub fn into_mut(self) -> &'a mut T {
+    unsafe { self.sparse_vec.values.get_unchecked_mut(self.value_index) }
+  }
+  /// Stores `value` and returns the previously stored value.
+  pub fn insert(&mut self, value: T) -> T { mem::replace(self.get_mut(),value) }
+  /// Unsets the entry and returns the stored value.
+  pub fn remove(self) -> T {
+    self.sparse_vec.indices.remove(self.value_index);
+    self.sparse_vec.values.remove(self.value_index)
+  }
+}
+
+/// A view into an unset index of a [SparseVec].
+pub struct VacantEntry<'a, T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// SparseVec to store the value in.
+  pub(super) sparse_vec: &'a mut SparseVec<T, Alloc, Idx>,
+  /// External index of the entry.
+  pub(super) index: Idx,
+  /// Position to insert the value into `sparse_vec.values`.
+  ///
+  /// # Invariants
+  ///
+  /// * Inserting `index` at `value_index` keeps `sparse_vec.indices` sorted.
+  pub(super) value_index: usize,
+}
+
+impl<'a, T, Alloc, Idx> VacantEntry<'a, T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy {
+  /// Returns the external index of the entry.
+  pub fn index(&self) -> Idx { self.index }
+  /// Stores `value` and returns the stored value.
+  pub fn insert(self, value: T) -> &'a mut T {
+    self.sparse_vec.indices.insert(self.value_index,self.index);
+    self.sparse_vec.values.insert(self.value_index,value);
+
+    unsafe { self.sparse_vec.values.get_unchecked_mut(self.value_index) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::alloc::Global;
+  use alloc::vec;
+  use alloc::vec::Vec;
+
+  /// SparseVec of the tests.
+  type TestVec<T> = SparseVec<T, Global>;
+
+  /// Collects the `(index, value)` pairs of `sparse_vec`.
+  fn pairs(sparse_vec: &TestVec<u32>) -> Vec<(usize, u32)> {
+    sparse_vec.iter().map(|(index,&value)| (index,value)).collect()
+  }
+
+  #[test]
+  fn or_insert() {
+    let mut sparse_vec: TestVec<u32> = [(1,1),(9,3)].into_iter().collect();
+
+    assert_eq!(*sparse_vec.entry(9).or_insert(30),3);
+    *sparse_vec.entry(4).or_insert(20) += 1;
+    assert_eq!(*sparse_vec.entry(0).or_insert_with(|| 10),10);
+    assert_eq!(*sparse_vec.entry(1).or_insert_with(|| panic!("called on a set index")),1);
+    assert_eq!(*sparse_vec.entry(12).or_default(),0);
+    assert_eq!(pairs(&sparse_vec),vec![(0,10),(1,1),(4,21),(9,3),(12,0)]);
+  }
+  #[test]
+  fn and_modify() {
+    let mut sparse_vec: TestVec<u32> = [(1,1),(9,3)].into_iter().collect();
+
+    sparse_vec.entry(9).and_modify(|value| *value *= 10).or_insert(0);
+    sparse_vec.entry(5).and_modify(|value| *value *= 10).or_insert(7);
+    assert_eq!(pairs(&sparse_vec),vec![(1,1),(5,7),(9,30)]);
+  }
+  #[test]
+  fn occupied() {
+    let mut sparse_vec: TestVec<u32> = [(1,1),(4,2),(9,3)].into_iter().collect();
+    let Entry::Occupied(mut entry) = sparse_vec.entry(4) else { panic!("vacant set index") };
+
+    assert_eq!((entry.index(),*entry.get()),(4,2));
+    assert_eq!(entry.insert(20),2);
+    *entry.get_mut() += 1;
+    assert_eq!(entry.remove(),21);
+    assert_eq!(pairs(&sparse_vec),vec![(1,1),(9,3)]);
+  }
+  #[test]
+  fn vacant_position() {
+    for index in [0,2,5,10] {
+      let mut sparse_vec: TestVec<u32> = [(1,1),(4,2),(9,3)].into_iter().collect();
+      let Entry::Vacant(entry) = sparse_vec.entry(index) else { panic!("occupied unset index") };
+
+      assert_eq!(entry.index(),index);
+      *entry.insert(100) += 1;
+
+      let mut expected = vec![(1,1),(4,2),(9,3),(index,101)];
+
+      expected.sort();
+      assert_eq!(pairs(&sparse_vec),expected);
+    }
+  }
+}
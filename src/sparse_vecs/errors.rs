@@ -0,0 +1,157 @@
+//! Defines the errors of [SparseVec](super::SparseVec).
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+
+use alloc::alloc::Allocator;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{self,Debug,Display};
+
+/// Error of [move_entry](super::SparseVec::move_entry) when the index to move from is unset.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct MoveError<Idx = usize> {
+  /// External index which was unset.
+  pub from: Idx,
+}
+
+impl<Idx> Display for MoveError<Idx>
+  where Idx: Display {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    write!(fmt,"moved from the empty index {}",self.from)
+  }
+}
+
+impl<Idx> Error for MoveError<Idx>
+  where Idx: Debug + Display {}
+
+/// Error of [try_from_parts](super::SparseVec::try_from_parts) when the parts break the invariants
+/// of a SparseVec.
+///
+/// Holds the rejected parts so that they can be recovered.
+pub struct FromPartsError<T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// Invariant which was broken.
+  pub kind: FromPartsErrorKind,
+  /// Rejected external indices.
+  pub indices: Vec<Idx, Alloc>,
+  /// Rejected values.
+  pub values: Vec<T, Alloc>,
+}
+
+impl<T, Alloc, Idx> FromPartsError<T, Alloc, Idx>
+  where Alloc: Allocator {
+  /// Deconstructs the error into the rejected parts.
+  ///
+  /// Returns `(Indices, Values)`.
+  pub fn into_parts(self) -> (Vec<Idx, Alloc>, Vec<T, Alloc>) { (self.indices,self.values) }
+}
+
+impl<T, Alloc, Idx> Debug for FromPartsError<T, Alloc, Idx>
+  where T: Debug, Alloc: Allocator, Idx: Debug {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    fmt.debug_struct("FromPartsError")
+      .field("kind",&self.kind)
+      .field("indices",&self.indices)
+      .field("values",&self.values)
+      .finish()
+  }
+}
+
+impl<T, Alloc, Idx> Display for FromPartsError<T, Alloc, Idx>
+  where Alloc: Allocator {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result { Display::fmt(&self.kind,fmt) }
+}
+
+impl<T, Alloc, Idx> Error for FromPartsError<T, Alloc, Idx>
+  where T: Debug, Alloc: Allocator, Idx: Debug {}
+
+/// Invariant broken by the parts of a [FromPartsError].
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum FromPartsErrorKind {
+  /// The indices and values have different lengths.
+  LengthMismatch {
+    /// Length of the indices.
+    indices: usize,
+    /// Length of the values.
+    values: usize,
+  },
+  /// An index is less than the index before it.
+  NotSorted {
+    /// Position of the index among the indices.
+    at: usize,
+  },
+  /// An index is equal to the index before it.
+  Duplicate {
+    /// Position of the repeated index among the indices.
+    at: usize,
+  },
+}
+
+impl Display for FromPartsErrorKind {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::LengthMismatch{indices,values} => write!(fmt,"got {indices} indices and {values} values"),
+      Self::NotSorted{at} => write!(fmt,"the index at position {at} was unsorted"),
+      Self::Duplicate{at} => write!(fmt,"the index at position {at} was repeated"),
+    }
+  }
+}
+
+/// Error of [from_sorted_iter](super::SparseVec::from_sorted_iter) when an index is not greater
+/// than the index before it.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct SortedInputError<Idx = usize> {
+  /// Position of the pair in the input.
+  pub position: usize,
+  /// External index of the pair.
+  pub index: Idx,
+}
+
+impl<Idx> Display for SortedInputError<Idx>
+  where Idx: Display {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    write!(fmt,"the index {} at position {} was not greater than the index before it",self.index,self.position)
+  }
+}
+
+impl<Idx> Error for SortedInputError<Idx>
+  where Idx: Debug + Display {}
+
+/// Error of [remap_indices](super::SparseVec::remap_indices) when two indices are mapped to the
+/// same index.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct DuplicateIndexError<Idx = usize> {
+  /// External index which was repeated.
+  pub index: Idx,
+}
+
+impl<Idx> Display for DuplicateIndexError<Idx>
+  where Idx: Display {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    write!(fmt,"repeated the index {}",self.index)
+  }
+}
+
+impl<Idx> Error for DuplicateIndexError<Idx>
+  where Idx: Debug + Display {}
+
+/// Error of [offset_indices](super::SparseVec::offset_indices) when an index leaves the range of `usize`.
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum OffsetError {
+  /// The lowest index would be less than `0`.
+  Underflow,
+  /// The highest index would be greater than `usize::MAX`.
+  Overflow,
+}
+
+impl Display for OffsetError {
+  fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Underflow => fmt.write_str("offset an index below `0`"),
+      Self::Overflow => fmt.write_str("offset an index past `usize::MAX`"),
+    }
+  }
+}
+
+impl Error for OffsetError {}
@@ -0,0 +1,200 @@
+//! Defines the iterators of [SparseVec].
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+
+use super::SparseVec;
+use alloc::alloc::Allocator;
+use alloc::vec;
+use core::iter::FusedIterator;
+use core::ptr;
+
+/// Owning iterator over the set indices of a [SparseVec].
+///
+/// Constructed by [SparseVec::into_iter].
+pub struct IntoIter<T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// Remaining external indices.
+  ///
+  /// # Invariants
+  ///
+  /// * Parallel iterator with `values`.
+  indices: vec::IntoIter<Idx,Alloc>,
+  /// Remaining values.
+  ///
+  /// # Invariants
+  ///
+  /// * Parallel iterator with `indices`.
+  values: vec::IntoIter<T,Alloc>,
+}
+
+impl<T, Alloc, Idx> Iterator for IntoIter<T, Alloc, Idx>
+  where Alloc: Allocator {
+  type Item = (Idx, T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    Some((self.indices.next()?,self.values.next()?))
+  }
+  fn size_hint(&self) -> (usize, Option<usize>) { self.indices.size_hint() }
+}
+
+impl<T, Alloc, Idx> DoubleEndedIterator for IntoIter<T, Alloc, Idx>
+  where Alloc: Allocator {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    Some((self.indices.next_back()?,self.values.next_back()?))
+  }
+}
+
+impl<T, Alloc, Idx> ExactSizeIterator for IntoIter<T, Alloc, Idx>
+  where Alloc: Allocator {}
+
+impl<T, Alloc, Idx> FusedIterator for IntoIter<T, Alloc, Idx>
+  where Alloc: Allocator {}
+
+impl<T, Alloc, Idx> IntoIterator for SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  type Item = (Idx, T);
+  type IntoIter = IntoIter<T, Alloc, Idx>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    let (indices,values) = self.into_parts();
+
+    IntoIter{indices: indices.into_iter(),values: values.into_iter()}
+  }
+}
+
+/// Draining iterator over the set indices of a [SparseVec].
+///
+/// Constructed by [SparseVec::drain] and [SparseVec::drain_range].
+pub struct Drain<'a, T, Alloc, Idx = usize>
+  where Alloc: Allocator {
+  /// Remaining external indices.
+  ///
+  /// # Invariants
+  ///
+  /// * Parallel iterator with `values`.
+  pub(super) indices: vec::Drain<'a,Idx,Alloc>,
+  /// Remaining values.
+  ///
+  /// # Invariants
+  ///
+  /// * Parallel iterator with `indices`.
+  pub(super) values: vec::Drain<'a,T,Alloc>,
+}
+
+impl<T, Alloc, Idx> Iterator for Drain<'_, T, Alloc, Idx>
+  where Alloc: Allocator {
+  type Item = (Idx, T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    Some((self.indices.next()?,self.values.next()?))
+  }
+  fn size_hint(&self) -> (usize, Option<usize>) { self.indices.size_hint() }
+}
+
+impl<T, Alloc, Idx> DoubleEndedIterator for Drain<'_, T, Alloc, Idx>
+  where Alloc: Allocator {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    Some((self.indices.next_back()?,self.values.next_back()?))
+  }
+}
+
+impl<T, Alloc, Idx> ExactSizeIterator for Drain<'_, T, Alloc, Idx>
+  where Alloc: Allocator {}
+
+impl<T, Alloc, Idx> FusedIterator for Drain<'_, T, Alloc, Idx>
+  where Alloc: Allocator {}
+
+/// Iterator which unsets and yields the `(index, value)` pairs matching a filter.
+///
+/// Constructed by [SparseVec::extract_if].
+pub struct ExtractIf<'a, T, Alloc, F, Idx = usize>
+  where Alloc: Allocator {
+  /// SparseVec being filtered.
+  ///
+  /// # Invariants
+  ///
+  /// * Length of `indices` and `values` is `0` until dropped.
+  sparse_vec: &'a mut SparseVec<T, Alloc, Idx>,
+  /// Tests if `(index, value)` should be extracted.
+  filter: F,
+  /// Count of values kept at the front.
+  kept: usize,
+  /// Count of values visited.
+  processed: usize,
+  /// Count of values before filtering.
+  len: usize,
+}
+
+impl<'a, T, Alloc, F, Idx> ExtractIf<'a, T, Alloc, F, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  /// Constructs a new ExtractIf.
+  ///
+  /// # Params
+  ///
+  /// sparse_vec --- SparseVec to filter.  
+  /// filter --- Tests if `(index, value)` should be extracted.  
+  pub(super) fn new(sparse_vec: &'a mut SparseVec<T, Alloc, Idx>, filter: F) -> Self {
+    let len = sparse_vec.count();
+
+    // Leaking the iterator leaks the values rather than exposing moved values.
+    unsafe {
+      sparse_vec.indices.set_len(0);
+      sparse_vec.values.set_len(0);
+    }
+
+    Self{sparse_vec,filter,kept: 0,processed: 0,len}
+  }
+}
+
+impl<T, Alloc, F, Idx> Iterator for ExtractIf<'_, T, Alloc, F, Idx>
+  where Alloc: Allocator, Idx: Copy, F: FnMut(Idx, &mut T) -> bool {
+  type Item = (Idx, T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let indices = self.sparse_vec.indices.as_mut_ptr();
+    let values = self.sparse_vec.values.as_mut_ptr();
+
+    while self.processed < self.len {
+      let position = self.processed;
+      let index = unsafe { *indices.add(position) };
+      let extract = (self.filter)(index,unsafe { &mut *values.add(position) });
+
+      self.processed += 1;
+      if extract { return Some((index,unsafe { ptr::read(values.add(position)) })) }
+
+      if self.kept != position {
+        unsafe {
+          ptr::copy_nonoverlapping(indices.add(position),indices.add(self.kept),1);
+          ptr::copy_nonoverlapping(values.add(position),values.add(self.kept),1);
+        }
+      }
+      self.kept += 1;
+    }
+
+    None
+  }
+  fn size_hint(&self) -> (usize, Option<usize>) { (0,Some(self.len - self.processed)) }
+}
+
+impl<T, Alloc, F, Idx> FusedIterator for ExtractIf<'_, T, Alloc, F, Idx>
+  where Alloc: Allocator, Idx: Copy, F: FnMut(Idx, &mut T) -> bool {}
+
+impl<T, Alloc, F, Idx> Drop for ExtractIf<'_, T, Alloc, F, Idx>
+  where Alloc: Allocator {
+  fn drop(&mut self) {
+    let indices = self.sparse_vec.indices.as_mut_ptr();
+    let values = self.sparse_vec.values.as_mut_ptr();
+    let tail = self.len - self.processed;
+
+    unsafe {
+      if self.kept != self.processed {
+        ptr::copy(indices.add(self.processed),indices.add(self.kept),tail);
+        ptr::copy(values.add(self.processed),values.add(self.kept),tail);
+      }
+
+      self.sparse_vec.indices.set_len(self.kept + tail);
+      self.sparse_vec.values.set_len(self.kept + tail);
+    }
+  }
+}
@@ -0,0 +1,102 @@
+//! Defines the arithmetic of [SparseVec].
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+
+use super::SparseVec;
+use alloc::alloc::Allocator;
+use core::ops::{Add,AddAssign,Mul,MulAssign};
+
+impl<T, Alloc, Idx> SparseVec<T, Alloc, Idx>
+  where Alloc: Allocator, Idx: Copy + Ord {
+  /// Returns the sum of `self[index] * other[index]` over the indices set in both.
+  ///
+  /// The sum starts from `T::default()`, which is zero for the numeric types.
+  ///
+  /// # Params
+  ///
+  /// other --- SparseVec to multiply with.  
+  pub fn dot<T2, Alloc2>(&self, other: &SparseVec<T2, Alloc2, Idx>) -> T
+    where T: Default + AddAssign, for<'a> &'a T: Mul<&'a T2, Output = T>, Alloc2: Allocator {
+    let mut sum = T::default();
+
+    for (_,lhs,rhs) in self.intersection(other) { sum += lhs * rhs }
+
+    sum
+  }
+  /// Multiplies every stored value by `scalar`.
+  ///
+  /// # Params
+  ///
+  /// scalar --- Value to multiply by.  
+  pub fn scale<S>(&mut self, scalar: S)
+    where T: MulAssign<S>, S: Clone {
+    for value in &mut self.values { *value *= scalar.clone() }
+  }
+}
+
+impl<T, Alloc, Idx> Add for SparseVec<T, Alloc, Idx>
+  where T: Add<Output = T>, Alloc: Allocator + Clone, Idx: Copy + Ord {
+  type Output = Self;
+
+  /// Sums the values at each index, treating unset indices as the additive identity.
+  ///
+  /// Sums are kept even if they are zero.
+  fn add(mut self, rhs: Self) -> Self::Output {
+    self.merge_sorted(rhs,|_,lhs,rhs| lhs + rhs);
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::alloc::Global;
+  use alloc::vec;
+  use alloc::vec::Vec;
+
+  /// SparseVec of the tests.
+  type TestVec<T> = SparseVec<T, Global>;
+
+  /// Collects the `(index, value)` pairs of `sparse_vec`.
+  fn pairs(sparse_vec: &TestVec<i32>) -> Vec<(usize, i32)> {
+    sparse_vec.iter().map(|(index,&value)| (index,value)).collect()
+  }
+
+  #[test]
+  fn add_overlapping() {
+    let lhs: TestVec<i32> = [(1,1),(4,2),(9,3)].into_iter().collect();
+    let rhs: TestVec<i32> = [(0,10),(4,-2),(9,30)].into_iter().collect();
+
+    assert_eq!(pairs(&(lhs + rhs)),vec![(0,10),(1,1),(4,0),(9,33)]);
+  }
+  #[test]
+  fn add_disjoint() {
+    let lhs: TestVec<i32> = [(1,1),(4,2)].into_iter().collect();
+    let rhs: TestVec<i32> = [(2,10),(7,20)].into_iter().collect();
+
+    assert_eq!(pairs(&(lhs.clone() + rhs.clone())),vec![(1,1),(2,10),(4,2),(7,20)]);
+    assert_eq!(pairs(&(rhs + lhs.clone())),vec![(1,1),(2,10),(4,2),(7,20)]);
+    assert_eq!(pairs(&(lhs + TestVec::new())),vec![(1,1),(4,2)]);
+  }
+  #[test]
+  fn dot_overlapping_and_disjoint() {
+    let lhs: TestVec<i32> = [(1,2),(4,3),(9,5)].into_iter().collect();
+    let rhs: TestVec<i32> = [(0,7),(4,10),(9,-1)].into_iter().collect();
+    let disjoint: TestVec<i32> = [(2,7),(5,11)].into_iter().collect();
+
+    assert_eq!(lhs.dot(&rhs),25);
+    assert_eq!(rhs.dot(&lhs),25);
+    assert_eq!(lhs.dot(&disjoint),0);
+    assert_eq!(lhs.dot(&TestVec::new()),0);
+  }
+  #[test]
+  fn scale_keeps_indices() {
+    let mut sparse_vec: TestVec<i32> = [(1,2),(4,-3),(9,5)].into_iter().collect();
+
+    sparse_vec.scale(3);
+    assert_eq!(pairs(&sparse_vec),vec![(1,6),(4,-9),(9,15)]);
+    sparse_vec.scale(0);
+    assert_eq!(pairs(&sparse_vec),vec![(1,0),(4,0),(9,0)]);
+  }
+}
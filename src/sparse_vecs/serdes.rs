@@ -0,0 +1,131 @@
+//! Defines the serde implementations of [SparseVec].
+//!
+//! A SparseVec is serialized as a map of `index -> value` in index order.
+//!
+//! Author --- DMorgan  
+//! Last Modified --- 2026-10-14
+
+use super::SparseVec;
+use alloc::alloc::Allocator;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+use core::marker::PhantomData;
+use serde::de::{self,Deserialize,Deserializer,MapAccess,Visitor};
+use serde::ser::{Serialize,Serializer};
+
+impl<T, Alloc, Idx> Serialize for SparseVec<T, Alloc, Idx>
+  where T: Serialize, Alloc: Allocator, Idx: Copy + Ord + Serialize {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+    serializer.collect_map(self.iter())
+  }
+}
+
+impl<'de, T, Alloc, Idx> Deserialize<'de> for SparseVec<T, Alloc, Idx>
+  where T: Deserialize<'de>, Alloc: Allocator + Default, Idx: Copy + Ord + Deserialize<'de> {
+  /// Deserializes a map of `index -> value`.
+  ///
+  /// Fails if the indices are not unique and sorted.
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+    deserializer.deserialize_map(SparseVecVisitor(PhantomData))
+  }
+}
+
+/// Most bytes to preallocate from the size hint of the input.
+const MAX_PREALLOCATION: usize = 1024 * 1024;
+
+/// Visits a map of `index -> value`.
+struct SparseVecVisitor<T, Alloc, Idx>(PhantomData<SparseVec<T, Alloc, Idx>>)
+  where Alloc: Allocator;
+
+impl<'de, T, Alloc, Idx> Visitor<'de> for SparseVecVisitor<T, Alloc, Idx>
+  where T: Deserialize<'de>, Alloc: Allocator + Default, Idx: Copy + Ord + Deserialize<'de> {
+  type Value = SparseVec<T, Alloc, Idx>;
+
+  fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+    fmt.write_str("a map of unique, sorted indices to values")
+  }
+  fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where M: MapAccess<'de> {
+    // The hint comes from the input so the preallocation is capped as serde caps its own.
+    let capacity = map.size_hint().unwrap_or(0).min(MAX_PREALLOCATION / mem::size_of::<(Idx, T)>().max(1));
+    let mut indices = Vec::with_capacity_in(capacity,Alloc::default());
+    let mut values = Vec::with_capacity_in(capacity,Alloc::default());
+
+    while let Some((index,value)) = map.next_entry::<Idx,T>()? {
+      if indices.last().is_some_and(|&last| last >= index) {
+        return Err(de::Error::custom("indices must be unique and sorted"))
+      }
+
+      indices.push(index);
+      values.push(value);
+    }
+
+    Ok(unsafe { SparseVec::from_parts(indices,values) })
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+  use super::*;
+  use alloc::alloc::Global;
+  use alloc::string::String;
+  use alloc::vec;
+
+  /// SparseVec of the tests.
+  type TestVec<T> = SparseVec<T, Global>;
+
+  #[test]
+  fn round_trip() {
+    let sparse_vec: TestVec<String> = [(1,"a".into()),(8,"b".into())].into_iter().collect();
+    let json = serde_json::to_string(&sparse_vec).unwrap();
+
+    assert_eq!(json,r#"{"1":"a","8":"b"}"#);
+    assert_eq!(serde_json::from_str::<TestVec<String>>(&json).unwrap(),sparse_vec);
+    assert_eq!(serde_json::from_str::<TestVec<String>>("{}").unwrap(),TestVec::<String>::new());
+  }
+  #[test]
+  fn round_trip_small_index() {
+    let sparse_vec: SparseVec<u8, Global, u16> = [(3,1),(300,2)].into_iter().collect();
+    let json = serde_json::to_string(&sparse_vec).unwrap();
+
+    assert_eq!(serde_json::from_str::<SparseVec<u8, Global, u16>>(&json).unwrap(),sparse_vec);
+  }
+  #[test]
+  fn reject_unsorted() {
+    assert!(serde_json::from_str::<TestVec<u8>>(r#"{"4":1,"2":2}"#).is_err());
+  }
+  #[test]
+  fn reject_duplicate() {
+    assert!(serde_json::from_str::<TestVec<u8>>(r#"{"4":1,"4":2}"#).is_err());
+  }
+  #[test]
+  fn cap_size_hint() {
+    use serde::de::value::{Error,MapDeserializer};
+
+    /// Map which claims to hold far more entries than it does.
+    struct Lying<'de>(MapDeserializer<'de, vec::IntoIter<(usize, u8)>, Error>);
+
+    impl<'de> MapAccess<'de> for Lying<'de> {
+      type Error = Error;
+
+      fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+        where K: de::DeserializeSeed<'de> {
+        self.0.next_key_seed(seed)
+      }
+      fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+        where V: de::DeserializeSeed<'de> {
+        self.0.next_value_seed(seed)
+      }
+      fn size_hint(&self) -> Option<usize> { Some(usize::MAX) }
+    }
+
+    let map = Lying(MapDeserializer::new(vec![(2,7)].into_iter()));
+    let sparse_vec: TestVec<u8> = SparseVecVisitor(PhantomData).visit_map(map).unwrap();
+
+    assert_eq!(sparse_vec.as_parts(),(&[2][..],&[7][..]));
+    assert!(sparse_vec.capacity() <= MAX_PREALLOCATION);
+  }
+}